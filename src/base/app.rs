@@ -6,15 +6,21 @@ use crate::base::network::{
 };
 use crate::base::setup::base_handle_setup;
 use crate::base::spawn::base_handle_spawn_event;
-use crate::behaviour::collideable::handle_collision_event;
-use crate::behaviour::collideable::CollisionEvent;
+use crate::behaviour::collideable::{CollisionEvent, OwnerFilter};
 use crate::behaviour::expireable::handle_expireable;
 use crate::behaviour::weaponized::FireEvent;
-use crate::constants::{BACKGROUND_COLOR, BASE_TIME_STEP, BOUNDS, PIXELS_PER_METER, TITLE};
+use crate::constants::{
+    BACKGROUND_COLOR, BASE_TIME_STEP, BOUNDS, PIXELS_PER_METER, TITLE, WINDOW_SIZE,
+};
+use crate::identity::asset_cache::{handle_asset_cache, handle_asset_cache_setup, AssetCache};
+use crate::identity::entity::get_entity_kinds;
 use crate::identity::game::Game;
-use crate::identity::particle::handle_particle;
+use crate::identity::round::{MatchState, Round};
+use crate::identity::scoreboard::Scoreboard;
 use crate::types::event::{
-    DespawnEvent, InputEvent, JoinEvent, LeaveEvent, SpawnEvent, UpdateEvent,
+    DespawnEvent, EffectEvent, GameModeEvent, HelloEvent, InputEvent, JoinEvent, KillEvent,
+    LeaveEvent, PingEvent, PongEvent, RoundEvent, ScoreboardEvent, SpawnEvent, UpdateEvent,
+    WeaponEvent,
 };
 use crate::types::network::{CloseEvent, IncomingMessageEvent, OpenEvent, OutgoingMessageEvent};
 use bevy::app::MainScheduleOrder;
@@ -22,13 +28,13 @@ use bevy::ecs::schedule::ScheduleLabel;
 use bevy::log::LogPlugin;
 use bevy::math::IVec2;
 use bevy::prelude::{
-    default, trace, App, ClearColor, Fixed, FixedUpdate, PluginGroup, Schedule, Startup, Time,
-    Update, Window, WindowPlugin,
+    default, trace, App, AppExtStates, ClearColor, Fixed, FixedUpdate, PluginGroup, Schedule,
+    Startup, Time, Update, Window, WindowPlugin,
 };
 use bevy::window::WindowPosition::At;
 use bevy::window::{PresentMode, WindowResolution};
 use bevy::DefaultPlugins;
-use bevy_rapier2d::prelude::{NoUserData, RapierPhysicsPlugin};
+use bevy_rapier2d::prelude::RapierPhysicsPlugin;
 use std::collections::HashSet;
 
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
@@ -57,15 +63,17 @@ pub fn get_base_app() -> App {
             .set(WindowPlugin {
                 primary_window: Some(Window {
                     title: TITLE.to_string(),
-                    resolution: WindowResolution::new(BOUNDS.x, BOUNDS.y),
+                    resolution: WindowResolution::new(WINDOW_SIZE.x, WINDOW_SIZE.y),
                     present_mode: PresentMode::Fifo,
                     position: At(IVec2::new(0, 0)),
+                    // fill whatever the canvas is in (the page, for the WASM client)
+                    fit_canvas_to_parent: true,
                     ..default()
                 }),
                 ..default()
             })
             .add(bevy_framepace::FramepacePlugin)
-            .add(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(
+            .add(RapierPhysicsPlugin::<OwnerFilter>::pixels_per_meter(
                 PIXELS_PER_METER,
             ))
             .add(LogPlugin {
@@ -87,6 +95,24 @@ pub fn get_base_app() -> App {
         last_update: 0.0,
         server_time_at_join: 0.0,
         client_time_at_join: 0.0,
+        is_connected: false,
+        last_ping_at: 0.0,
+        round_trip_time: None,
+        is_wrapping: true,
+        arena_size: BOUNDS,
+    });
+
+    app.init_state::<MatchState>();
+
+    app.insert_resource(get_entity_kinds());
+    app.insert_resource(AssetCache::default());
+
+    app.insert_resource(Round::default());
+
+    app.insert_resource(Scoreboard {
+        entries: vec![],
+        teams: vec![],
+        last_update_at: 0.0,
     });
 
     let network_transition = Schedule::new(NetworkTransition);
@@ -132,6 +158,7 @@ pub fn get_base_app() -> App {
         .insert_before(BaseNetworkTransition, NetworkTransition);
 
     app.add_systems(Startup, base_handle_setup);
+    app.add_systems(Startup, handle_asset_cache_setup);
 
     // register network events
     app.add_event::<OpenEvent>();
@@ -140,6 +167,7 @@ pub fn get_base_app() -> App {
     app.add_event::<CloseEvent>();
 
     // register game events
+    app.add_event::<HelloEvent>();
     app.add_event::<JoinEvent>();
     app.add_event::<SpawnEvent>();
     app.add_event::<InputEvent>();
@@ -148,6 +176,14 @@ pub fn get_base_app() -> App {
     app.add_event::<LeaveEvent>();
     app.add_event::<FireEvent>();
     app.add_event::<CollisionEvent>();
+    app.add_event::<PingEvent>();
+    app.add_event::<PongEvent>();
+    app.add_event::<ScoreboardEvent>();
+    app.add_event::<WeaponEvent>();
+    app.add_event::<GameModeEvent>();
+    app.add_event::<EffectEvent>();
+    app.add_event::<KillEvent>();
+    app.add_event::<RoundEvent>();
 
     // handlers to wire base network events into server / client game events
     app.add_systems(BaseNetworkTransition, base_handle_open_event);
@@ -159,12 +195,13 @@ pub fn get_base_app() -> App {
     app.add_systems(AfterNetworkTransition1, base_handle_leave_event);
     app.add_systems(AfterNetworkTransition2, base_handle_spawn_event);
     app.add_systems(AfterNetworkTransition2, base_handle_despawn_event);
-    app.add_systems(AfterNetworkTransition3, handle_collision_event);
 
     // handlers to calculate game state per time step
-    app.add_systems(FixedUpdate, handle_particle);
     app.add_systems(FixedUpdate, handle_expireable);
 
+    // handler to let go of cached assets that nothing's using any more
+    app.add_systems(Update, handle_asset_cache);
+
     trace!("base.get_app(); returning app={:?}", app);
 
     app
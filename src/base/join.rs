@@ -21,6 +21,8 @@ pub fn base_handle_join_event(
             game.local_player_uuid = Some(join.player_uuid);
             game.server_time_at_join = join.server_time;
             game.client_time_at_join = time.elapsed_seconds_f64();
+            game.is_wrapping = join.is_wrapping;
+            game.arena_size = join.arena_size;
         }
 
         trace!("base_handle_join_event; game={:?}", game);
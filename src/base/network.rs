@@ -1,9 +1,13 @@
-use bevy::prelude::{EventReader, EventWriter};
+use bevy::prelude::{warn, Event, EventReader, EventWriter, Res};
 
 use crate::base::helpers::deserialize;
 use crate::behaviour::collideable::CollisionEvent;
+use crate::constants::CLIENT_MESSAGE_TYPES;
+use crate::identity::game::Game;
 use crate::types::event::{
-    DespawnEvent, InputEvent, JoinEvent, LeaveEvent, SpawnEvent, UpdateEvent,
+    DespawnEvent, EffectEvent, GameModeEvent, HelloEvent, InputEvent, JoinEvent, KillEvent,
+    LeaveEvent, PingEvent, PongEvent, RoundEvent, ScoreboardEvent, SpawnEvent, UpdateEvent,
+    WeaponEvent,
 };
 use crate::types::network::{CloseEvent, Container, IncomingMessageEvent, OpenEvent};
 
@@ -13,8 +17,27 @@ pub fn base_handle_open_event(mut open_event_reader: EventReader<OpenEvent>) {
     }
 }
 
+// a message without the payload its type calls for is dropped, rather than taking the router down
+fn send_payload<E: Event>(
+    message_type: &str,
+    payload: Option<E>,
+    event_writer: &mut EventWriter<E>,
+) {
+    if payload.is_none() {
+        warn!(
+            "base_handle_incoming_message_event; missing payload for message_type={:?}",
+            message_type
+        );
+        return;
+    }
+
+    event_writer.send(payload.unwrap());
+}
+
 pub fn base_handle_incoming_message_event(
     mut incoming_message_event_reader: EventReader<IncomingMessageEvent>,
+    game: Res<Game>,
+    mut hello_event_writer: EventWriter<HelloEvent>,
     mut join_event_writer: EventWriter<JoinEvent>,
     mut spawn_event_writer: EventWriter<SpawnEvent>,
     mut input_event_writer: EventWriter<InputEvent>,
@@ -22,24 +45,88 @@ pub fn base_handle_incoming_message_event(
     mut leave_event_writer: EventWriter<LeaveEvent>,
     mut despawn_event_writer: EventWriter<DespawnEvent>,
     mut collision_event_writer: EventWriter<CollisionEvent>,
+    mut ping_event_writer: EventWriter<PingEvent>,
+    mut pong_event_writer: EventWriter<PongEvent>,
+    mut scoreboard_event_writer: EventWriter<ScoreboardEvent>,
+    mut kill_event_writer: EventWriter<KillEvent>,
+    mut round_event_writer: EventWriter<RoundEvent>,
+    // grouped to stay under bevy's limit on system parameters
+    (mut weapon_event_writer, mut game_mode_event_writer, mut effect_event_writer): (
+        EventWriter<WeaponEvent>,
+        EventWriter<GameModeEvent>,
+        EventWriter<EffectEvent>,
+    ),
 ) {
     for incoming_message_event in incoming_message_event_reader.read() {
         let container = deserialize::<Container>(incoming_message_event.message.clone());
+        let message_type = container.message_type.as_str();
+
+        // the router is shared, but a client has no business telling the server about kills,
+        // rounds, collisions etc
+        if game.role == "server" && !CLIENT_MESSAGE_TYPES.contains(&message_type) {
+            warn!(
+                "base_handle_incoming_message_event; dropping message_type={:?} from session_uuid={:?}",
+                message_type, incoming_message_event.session_uuid
+            );
+            continue;
+        }
+
+        if message_type == "hello" {
+            // only trust the session the hello actually arrived on
+            let hello = container.hello.map(|mut hello| {
+                hello.session_uuid = incoming_message_event.session_uuid;
+                hello
+            });
+
+            send_payload(message_type, hello, &mut hello_event_writer);
+        } else if message_type == "join" {
+            send_payload(message_type, container.join, &mut join_event_writer);
+        } else if message_type == "spawn" {
+            send_payload(message_type, container.spawn, &mut spawn_event_writer);
+        } else if message_type == "input" {
+            send_payload(message_type, container.input, &mut input_event_writer);
+        } else if message_type == "update" {
+            send_payload(message_type, container.update, &mut update_event_writer);
+        } else if message_type == "leave" {
+            send_payload(message_type, container.leave, &mut leave_event_writer);
+        } else if message_type == "despawn" {
+            send_payload(message_type, container.despawn, &mut despawn_event_writer);
+        } else if message_type == "collision" {
+            send_payload(
+                message_type,
+                container.collision,
+                &mut collision_event_writer,
+            );
+        } else if message_type == "ping" {
+            // likewise, a ping can only be for the session it arrived on
+            let ping = container.ping.map(|mut ping| {
+                ping.player_uuid = incoming_message_event.session_uuid;
+                ping
+            });
 
-        if container.message_type == "join" {
-            join_event_writer.send(container.join.unwrap());
-        } else if container.message_type == "spawn" {
-            spawn_event_writer.send(container.spawn.unwrap());
-        } else if container.message_type == "input" {
-            input_event_writer.send(container.input.unwrap());
-        } else if container.message_type == "update" {
-            update_event_writer.send(container.update.unwrap());
-        } else if container.message_type == "leave" {
-            leave_event_writer.send(container.leave.unwrap());
-        } else if container.message_type == "despawn" {
-            despawn_event_writer.send(container.despawn.unwrap());
-        } else if container.message_type == "collision" {
-            collision_event_writer.send(container.collision.unwrap());
+            send_payload(message_type, ping, &mut ping_event_writer);
+        } else if message_type == "pong" {
+            send_payload(message_type, container.pong, &mut pong_event_writer);
+        } else if message_type == "scoreboard" {
+            send_payload(
+                message_type,
+                container.scoreboard,
+                &mut scoreboard_event_writer,
+            );
+        } else if message_type == "kill" {
+            send_payload(message_type, container.kill, &mut kill_event_writer);
+        } else if message_type == "round" {
+            send_payload(message_type, container.round, &mut round_event_writer);
+        } else if message_type == "weapon" {
+            send_payload(message_type, container.weapon, &mut weapon_event_writer);
+        } else if message_type == "game_mode" {
+            send_payload(
+                message_type,
+                container.game_mode,
+                &mut game_mode_event_writer,
+            );
+        } else if message_type == "effect" {
+            send_payload(message_type, container.effect, &mut effect_event_writer);
         }
     }
 }
@@ -1,11 +1,20 @@
-use bevy::prelude::{Query, Transform, Vec3};
+use bevy::prelude::{Query, Res, Transform, Vec3};
 
 use crate::behaviour::moveable::Moveable;
-use crate::constants::{BOUNDS, HALF};
+use crate::constants::HALF;
+use crate::identity::map::Map;
+
+pub fn handle_rollover_for_moveable(
+    map: Res<Map>,
+    mut moveable_query: Query<(&mut Moveable, &mut Transform)>,
+) {
+    // nothing gets past solid edges, so nothing rolls over (and had_rollover is left to respawns)
+    if map.is_solid() {
+        return;
+    }
 
-pub fn handle_rollover_for_moveable(mut moveable_query: Query<(&mut Moveable, &mut Transform)>) {
     for (mut moveable, mut transform) in moveable_query.iter_mut() {
-        let extents: Vec3 = Vec3::from((BOUNDS * HALF, 0.0));
+        let extents: Vec3 = Vec3::from((map.size * HALF, 0.0));
 
         let right = extents.x;
         let left = -extents.x;
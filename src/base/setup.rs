@@ -1,9 +1,12 @@
-use bevy::prelude::{Camera2dBundle, Commands, Component, ResMut, Vec2};
+use bevy::prelude::{
+    default, Camera2dBundle, Commands, Component, OrthographicProjection, ResMut, Vec2,
+};
+use bevy::render::camera::ScalingMode;
 use bevy_framepace::{FramepaceSettings, Limiter};
 use bevy_rapier2d::prelude::RapierConfiguration;
 use bevy_rapier2d::prelude::TimestepMode::Fixed;
 
-use crate::constants::{BASE_TIME_STEP, ZERO};
+use crate::constants::{BASE_TIME_STEP, WINDOW_SIZE, ZERO};
 
 #[derive(Component, Debug)]
 pub struct MainCamera;
@@ -25,7 +28,20 @@ pub fn base_handle_setup(
     //     substeps: 1,
     // };
 
-    commands.spawn((Camera2dBundle::default(), MainCamera));
+    // always the same amount of the arena in view, however big the window is
+    commands.spawn((
+        Camera2dBundle {
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
+                    width: WINDOW_SIZE.x,
+                    height: WINDOW_SIZE.y,
+                },
+                ..Camera2dBundle::default().projection
+            },
+            ..default()
+        },
+        MainCamera,
+    ));
 
     framespace_settings.limiter = Limiter::from_framerate(60.0);
 }
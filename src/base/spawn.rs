@@ -1,20 +1,19 @@
 use bevy::asset::Assets;
-use bevy::prelude::{ColorMaterial, Commands, EventReader, Mesh, Query, Res, ResMut, Time};
+use bevy::prelude::{ColorMaterial, Commands, Entity, EventReader, Mesh, Query, Res, ResMut, Time};
 
-use crate::identity::entity::spawn_entity;
+use crate::behaviour::identifiable::Identifiable;
+use crate::identity::asset_cache::AssetCache;
+use crate::identity::entity::{spawn_entity, EntityKinds};
 use crate::identity::game::Game;
-use crate::identity::particle::Particle;
-use crate::identity::player::Player;
-use crate::identity::projectile::Projectile;
 use crate::types::event::SpawnEvent;
 
 pub fn base_handle_spawn_event(
     mut spawn_event_reader: EventReader<SpawnEvent>,
+    entity_kinds: Res<EntityKinds>,
     game: Res<Game>,
-    player_query: Query<&Player>,
-    projectile_query: Query<&Projectile>,
-    particle_query: Query<&Particle>,
+    identifiable_query: Query<(Entity, &Identifiable)>,
     time: Res<Time>,
+    mut asset_cache: ResMut<AssetCache>,
     mut meshes: ResMut<'_, Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut commands: Commands,
@@ -22,11 +21,11 @@ pub fn base_handle_spawn_event(
     for spawn_event in spawn_event_reader.read() {
         spawn_entity(
             spawn_event.clone(),
+            &entity_kinds,
             &game,
-            &player_query,
-            &projectile_query,
-            &particle_query,
+            &identifiable_query,
             *time,
+            &mut asset_cache,
             &mut meshes,
             &mut materials,
             &mut commands,
@@ -1,75 +1,221 @@
-use bevy::math::{Quat, Vec3};
-use bevy::prelude::{Color, Component, Event, EventReader, EventWriter, Query, Transform};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::{
+    Component, Entity, Event, EventReader, EventWriter, Has, Query, Res, Transform,
+};
+use bevy_rapier2d::geometry::{CollisionGroups, Group, Sensor, SolverFlags};
 use bevy_rapier2d::pipeline::CollisionEvent as RapierCollisionEvent;
+use bevy_rapier2d::pipeline::{BevyPhysicsHooks, PairFilterContextView};
 use bevy_rapier2d::prelude::Velocity;
-use rand::prelude::SliceRandom;
-use rand::thread_rng;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::constants::{DEGREES_MAX, PARTICLE_LINEAR_VELOCITY, ZERO};
-use crate::identity::player::Player;
-use crate::identity::projectile::Projectile;
-use crate::types::event::{SerializableTransform, SerializableVelocity, SpawnEvent};
+use crate::behaviour::identifiable::Identifiable;
+use crate::behaviour::wrappable::Ghost;
+use crate::identity::entity::EntityKinds;
+use crate::types::event::{SerializableTransform, SerializableVelocity};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Collider {
-    entity_uuid: Uuid,
-    entity_type: String,
-    transform: Option<SerializableTransform>,
-    velocity: Option<SerializableVelocity>,
+    pub entity_uuid: Uuid,
+    pub entity_type: String,
+    // what the entity's kind says it is, for telling collisions apart
+    pub collision_type: Option<CollisionType>,
+    pub transform: Option<SerializableTransform>,
+    pub velocity: Option<SerializableVelocity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollisionKind {
+    // two solid things bumped into each other
+    Contact,
+    // something passed through a sensor (e.g. a pickup); nothing actually hit anything
+    Intersection,
+    // something left a sensor it was passing through (e.g. a zone)
+    Separation,
+}
+
+// what part an entity plays in collisions; each entity kind declares its own (if any)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollisionType {
+    Player,
+    Projectile,
+    Asteroid,
+    Pickup,
+    Wall,
+    // the walls around the arena when its edges are solid
+    Boundary,
+    Zone,
 }
 
 #[derive(Event, Debug, Clone, Serialize, Deserialize)]
 pub struct CollisionEvent {
     pub collider_a: Collider,
     pub collider_b: Collider,
+    pub kind: CollisionKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
 pub struct Collideable {
     pub entity_uuid: Uuid,
+    // collisions with this are ignored (e.g. a projectile and the player that fired it); anything
+    // with an owner needs ActiveHooks for rapier to ask OwnerFilter about it
+    pub owner_uuid: Option<Uuid>,
+}
+
+// the physics hooks rapier runs its pairs through, so things don't bump into their owners at all
+#[derive(SystemParam)]
+pub struct OwnerFilter<'w, 's> {
+    collideable_query: Query<'w, 's, &'static Collideable>,
+}
+
+impl OwnerFilter<'_, '_> {
+    fn is_owner_pair(&self, entity_a: Entity, entity_b: Entity) -> bool {
+        let collideable_a = self.collideable_query.get(entity_a);
+        let collideable_b = self.collideable_query.get(entity_b);
+
+        if collideable_a.is_err() || collideable_b.is_err() {
+            return false;
+        }
+
+        let collideable_a = collideable_a.unwrap();
+        let collideable_b = collideable_b.unwrap();
+
+        collideable_a.owner_uuid == Some(collideable_b.entity_uuid)
+            || collideable_b.owner_uuid == Some(collideable_a.entity_uuid)
+    }
+}
+
+impl BevyPhysicsHooks for OwnerFilter<'_, '_> {
+    fn filter_contact_pair(&self, context: PairFilterContextView) -> Option<SolverFlags> {
+        if self.is_owner_pair(context.collider1(), context.collider2()) {
+            return None;
+        }
+
+        Some(SolverFlags::COMPUTE_IMPULSES)
+    }
+
+    fn filter_intersection_pair(&self, context: PairFilterContextView) -> bool {
+        !self.is_owner_pair(context.collider1(), context.collider2())
+    }
+}
+
+const PLAYER_GROUP: Group = Group::GROUP_1;
+const PROJECTILE_GROUP: Group = Group::GROUP_2;
+const ASTEROID_GROUP: Group = Group::GROUP_3;
+const PICKUP_GROUP: Group = Group::GROUP_4;
+const WALL_GROUP: Group = Group::GROUP_5;
+const ZONE_GROUP: Group = Group::GROUP_6;
+
+// what each type of collider is and what it's allowed to touch
+pub fn get_collision_groups(collision_type: CollisionType) -> CollisionGroups {
+    match collision_type {
+        CollisionType::Player => CollisionGroups::new(
+            PLAYER_GROUP,
+            PLAYER_GROUP
+                | PROJECTILE_GROUP
+                | ASTEROID_GROUP
+                | PICKUP_GROUP
+                | WALL_GROUP
+                | ZONE_GROUP,
+        ),
+        // projectiles pass through each other
+        CollisionType::Projectile => {
+            CollisionGroups::new(PROJECTILE_GROUP, PLAYER_GROUP | ASTEROID_GROUP | WALL_GROUP)
+        }
+        CollisionType::Asteroid => CollisionGroups::new(
+            ASTEROID_GROUP,
+            PLAYER_GROUP | PROJECTILE_GROUP | ASTEROID_GROUP | WALL_GROUP,
+        ),
+        // only players pick things up
+        CollisionType::Pickup => CollisionGroups::new(PICKUP_GROUP, PLAYER_GROUP),
+        // only players can hold a zone
+        CollisionType::Zone => CollisionGroups::new(ZONE_GROUP, PLAYER_GROUP),
+        CollisionType::Wall | CollisionType::Boundary => {
+            CollisionGroups::new(WALL_GROUP, PLAYER_GROUP | PROJECTILE_GROUP | ASTEROID_GROUP)
+        }
+    }
 }
 
 pub fn handle_rapier_collision_event(
     mut rapier_collision_event_reader: EventReader<RapierCollisionEvent>,
     query: Query<(
         &Collideable,
+        &Identifiable,
         Option<&Transform>,
         Option<&Velocity>,
-        Option<&Player>,
-        Option<&Projectile>,
+        Has<Sensor>,
     )>,
+    ghost_query: Query<(&Ghost, &Transform)>,
+    entity_kinds: Res<EntityKinds>,
     mut collision_event_writer: EventWriter<CollisionEvent>,
 ) {
     for rapier_collision_event in rapier_collision_event_reader.read() {
-        let mut entity_a = None;
-        let mut entity_b = None;
+        let (entity_a, entity_b, is_stopped) = match rapier_collision_event {
+            RapierCollisionEvent::Started(entity_a, entity_b, _) => (entity_a, entity_b, false),
+            RapierCollisionEvent::Stopped(entity_a, entity_b, _) => (entity_a, entity_b, true),
+        };
 
-        match rapier_collision_event {
-            RapierCollisionEvent::Started(_entity_a, _entity_b, _) => {
-                entity_a = Some(_entity_a);
-                entity_b = Some(_entity_b);
+        // a ghost stands in for its source, but the collision happened where the ghost is
+        let resolve_ghost = |entity: Entity| -> (Entity, Option<&Transform>) {
+            let ghost = ghost_query.get(entity);
+            if ghost.is_err() {
+                return (entity, None);
             }
-            RapierCollisionEvent::Stopped(_, _, _) => {}
+
+            let (ghost, transform) = ghost.unwrap();
+
+            (ghost.source, Some(transform))
         };
 
-        if entity_a.is_none() || entity_b.is_none() {
+        let (entity_a, ghost_transform_a) = resolve_ghost(*entity_a);
+        let (entity_b, ghost_transform_b) = resolve_ghost(*entity_b);
+
+        // something's ghost touching the thing itself
+        if entity_a == entity_b {
             continue;
         }
 
-        let entity_a = entity_a.unwrap();
-        let entity_b = entity_b.unwrap();
-
-        let result_a = query.get(*entity_a);
-        let result_b = query.get(*entity_b);
+        let result_a = query.get(entity_a);
+        let result_b = query.get(entity_b);
 
         if result_a.is_err() || result_b.is_err() {
             continue;
         }
 
-        let (collideable_a, _transform_a, _velocity_a, player_a, projectile_a) = result_a.unwrap();
-        let (collideable_b, _transform_b, _velocity_b, player_b, projectile_b) = result_b.unwrap();
+        let (collideable_a, identifiable_a, mut _transform_a, _velocity_a, is_sensor_a) =
+            result_a.unwrap();
+        let (collideable_b, identifiable_b, mut _transform_b, _velocity_b, is_sensor_b) =
+            result_b.unwrap();
+
+        // OwnerFilter has already dropped these, but not for ghosts (which only ever intersect)
+        if collideable_a.owner_uuid == Some(collideable_b.entity_uuid)
+            || collideable_b.owner_uuid == Some(collideable_a.entity_uuid)
+        {
+            continue;
+        }
+
+        // decided by the sources rather than rapier's flags, as ghosts are always sensors
+        let mut kind = CollisionKind::Contact;
+        if is_sensor_a || is_sensor_b {
+            kind = CollisionKind::Intersection;
+        }
+
+        // only sensors care about things leaving them
+        if is_stopped {
+            if kind == CollisionKind::Contact {
+                continue;
+            }
+
+            kind = CollisionKind::Separation;
+        }
+
+        if ghost_transform_a.is_some() {
+            _transform_a = ghost_transform_a;
+        }
+
+        if ghost_transform_b.is_some() {
+            _transform_b = ghost_transform_b;
+        }
 
         let mut transform_a = None;
         if _transform_a.is_some() {
@@ -95,94 +241,22 @@ pub fn handle_rapier_collision_event(
             velocity_b = Some(SerializableVelocity::from_velocity(*_velocity_b.unwrap()));
         }
 
-        let entity_type_a;
-        if player_a.is_some() {
-            entity_type_a = "player"
-        } else if projectile_a.is_some() {
-            entity_type_a = "projectile"
-        } else {
-            panic!(
-                "failed to infer entity_type_a from player_a={:?} and projectile_a={:?}",
-                player_a, projectile_a
-            );
-        }
-
-        let entity_type_b;
-        if player_b.is_some() {
-            entity_type_b = "player"
-        } else if projectile_b.is_some() {
-            entity_type_b = "projectile"
-        } else {
-            panic!(
-                "failed to infer entity_type_b from player_b={:?} and projectile_b={:?}",
-                player_b, projectile_b
-            );
-        }
-
         collision_event_writer.send(CollisionEvent {
             collider_a: Collider {
                 entity_uuid: collideable_a.entity_uuid,
-                entity_type: entity_type_a.to_string(),
+                entity_type: identifiable_a.entity_type.clone(),
+                collision_type: entity_kinds.get_collision_type(&identifiable_a.entity_type),
                 transform: transform_a,
                 velocity: velocity_a,
             },
             collider_b: Collider {
                 entity_uuid: collideable_b.entity_uuid,
-                entity_type: entity_type_b.to_string(),
+                entity_type: identifiable_b.entity_type.clone(),
+                collision_type: entity_kinds.get_collision_type(&identifiable_b.entity_type),
                 transform: transform_b,
                 velocity: velocity_b,
             },
+            kind,
         });
     }
 }
-
-pub fn handle_collision_event(
-    mut collision_event_reader: EventReader<CollisionEvent>,
-    mut spawn_event_writer: EventWriter<SpawnEvent>,
-) {
-    for collision in collision_event_reader.read() {
-        let mut transform_a = collision
-            .clone()
-            .collider_a
-            .transform
-            .unwrap()
-            .to_transform();
-
-        transform_a.rotation = Quat::default();
-
-        for i in 0..4 {
-            transform_a.rotation =
-                Quat::from_rotation_z(f32::to_radians((DEGREES_MAX / 8_f32) * i as f32));
-
-            let mut velocity_a = Velocity::zero();
-
-            velocity_a.linvel += transform_a
-                .rotation
-                .mul_vec3(Vec3::new(ZERO, PARTICLE_LINEAR_VELOCITY, ZERO))
-                .truncate();
-
-            let mut color = Color::srgb(1.0, 1.0, 1.0);
-
-            if collision.collider_a.entity_type == "player"
-                || collision.collider_b.entity_type == "player"
-            {
-                color = *[
-                    Color::srgb(1.0, 1.0, 0.0),
-                    Color::srgb(1.0, 0.65, 0.0),
-                    Color::srgb(1.0, 0.27, 0.0),
-                    Color::srgb(1.0, 0.0, 0.0),
-                ]
-                .choose(&mut thread_rng())
-                .unwrap_or(&Color::srgb(1.0, 1.0, 0.0));
-            }
-
-            spawn_event_writer.send(SpawnEvent {
-                entity_uuid: Uuid::new_v4(),
-                entity_type: "particle".to_string(),
-                transform: Some(SerializableTransform::from_transform(transform_a)),
-                velocity: Some(SerializableVelocity::from_velocity(velocity_a)),
-                color: Some(color),
-            });
-        }
-    }
-}
@@ -0,0 +1,31 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Damageable {
+    pub entity_uuid: Uuid,
+    pub health: f32,
+    pub max_health: f32,
+}
+
+impl Damageable {
+    // returns true if this damage was the killing blow
+    pub fn damage(self: &mut Damageable, amount: f32) -> bool {
+        if self.health <= 0.0 {
+            return false;
+        }
+
+        self.health = (self.health - amount).max(0.0);
+
+        self.health <= 0.0
+    }
+
+    pub fn heal(self: &mut Damageable, amount: f32) {
+        self.health = (self.health + amount).min(self.max_health);
+    }
+
+    pub fn reset(self: &mut Damageable) {
+        self.health = self.max_health;
+    }
+}
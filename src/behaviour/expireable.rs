@@ -1,10 +1,8 @@
-use bevy::prelude::{Component, Entity, EventWriter, Query, Res, Time};
+use bevy::prelude::{Component, EventWriter, Query, Res, Time};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::identity::particle::Particle;
-use crate::identity::player::Player;
-use crate::identity::projectile::Projectile;
+use crate::behaviour::identifiable::Identifiable;
 use crate::types::event::DespawnEvent;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
@@ -15,38 +13,17 @@ pub struct Expireable {
 
 pub fn handle_expireable(
     time: Res<Time>,
-    mut expireable_query: Query<(
-        Entity,
-        &Expireable,
-        Option<&Player>,
-        Option<&Projectile>,
-        Option<&Particle>,
-    )>,
+    expireable_query: Query<(&Expireable, &Identifiable)>,
     mut despawn_event_writer: EventWriter<DespawnEvent>,
 ) {
-    for (entity, expireable, player, projectile, particle) in expireable_query.iter_mut() {
+    for (expireable, identifiable) in expireable_query.iter() {
         if time.elapsed_seconds_f64() < expireable.expires_at {
             continue;
         }
 
-        let entity_type;
-
-        if player.is_some() {
-            entity_type = "player"
-        } else if projectile.is_some() {
-            entity_type = "projectile"
-        } else if particle.is_some() {
-            entity_type = "particle"
-        } else {
-            panic!(
-                "failed to identify entity_type for entity={:?}, expireable={:?}",
-                entity, expireable
-            );
-        }
-
         despawn_event_writer.send(DespawnEvent {
             entity_uuid: expireable.entity_uuid,
-            entity_type: entity_type.to_string(),
+            entity_type: identifiable.entity_type.clone(),
         });
     }
 }
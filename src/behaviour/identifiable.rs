@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// added to everything spawned through spawn_entity, so systems can find out what kind of entity
+// they're dealing with without querying for every kind's component
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Identifiable {
+    pub entity_uuid: Uuid,
+    pub entity_type: String,
+}
@@ -1,4 +1,7 @@
 pub mod collideable;
+pub mod damageable;
 pub mod expireable;
+pub mod identifiable;
 pub mod moveable;
 pub mod weaponized;
+pub mod wrappable;
@@ -1,77 +1,357 @@
-use bevy::math::Vec3;
-use bevy::prelude::{
-    Color, Component, Event, EventReader, EventWriter, Query, Res, Time, Transform,
-};
+use bevy::math::{Quat, Vec3};
+use bevy::prelude::{Component, Event, EventReader, EventWriter, Query, Res, Time, Transform};
 use bevy_rapier2d::prelude::Velocity;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::constants::{
-    MATERIAL_SCALE, PLAYER_HEIGHT_MULTIPLIER, PROJECTILE_LINEAR_VELOCITY, WEAPON_FIRE_RATE_SECONDS,
-    ZERO,
+    MATERIAL_SCALE, PLAYER_HEIGHT_MULTIPLIER, WEAPON_HEAT_COOLDOWN_PER_SECOND,
+    WEAPON_OVERHEAT_SECONDS, WEAPON_STARTING_TYPES, ZERO,
 };
+use crate::identity::effect::{get_effect_modifiers, Effect};
+use crate::identity::player::Player;
+use crate::identity::weapon::{get_weapon, Weapon};
 use crate::types::event::{SerializableTransform, SerializableVelocity, SpawnEvent};
 
 #[derive(Event, Debug, Clone, Serialize, Deserialize)]
 pub struct FireEvent {
     pub weapon_uuid: Uuid,
+    // sent once when fire is let go of; only charged weapons care
+    pub is_released: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
 pub struct Weaponized {
     pub weapon_uuid: Uuid,
     pub last_fired_at: f64,
+    // the weapons held, in the order they're switched through
+    pub weapon_types: Vec<String>,
+    pub weapon_index: usize,
+    pub charge_started_at: Option<f64>,
+    // set when a charged shot is let go of, and cleared once it's actually fired (the weapon might
+    // still be cooling down, reloading etc)
+    pub charge_released_at: Option<f64>,
+    // shots left for each held weapon (ignored for weapons without a magazine)
+    pub ammo: Vec<u32>,
+    pub reload_started_at: Option<f64>,
+    // 0.0 to 1.0, shared by every weapon that builds up heat
+    pub heat: f32,
+    pub overheated_until: Option<f64>,
+    pub last_update_at: f64,
+}
+
+fn get_full_ammo(weapon_type: &str) -> u32 {
+    get_weapon(weapon_type).magazine_size.unwrap_or(0)
 }
 
 impl Weaponized {
+    pub fn new(weapon_uuid: Uuid) -> Weaponized {
+        Weaponized {
+            weapon_uuid,
+            last_fired_at: 0.0,
+            weapon_types: WEAPON_STARTING_TYPES
+                .iter()
+                .map(|weapon_type| weapon_type.to_string())
+                .collect(),
+            weapon_index: 0,
+            charge_started_at: None,
+            charge_released_at: None,
+            ammo: WEAPON_STARTING_TYPES
+                .iter()
+                .map(|weapon_type| get_full_ammo(weapon_type))
+                .collect(),
+            reload_started_at: None,
+            heat: 0.0,
+            overheated_until: None,
+            last_update_at: 0.0,
+        }
+    }
+
+    pub fn get_weapon(self: &Weaponized) -> Weapon {
+        get_weapon(&self.weapon_types[self.weapon_index])
+    }
+
+    pub fn switch_weapon(self: &mut Weaponized) {
+        self.weapon_index = (self.weapon_index + 1) % self.weapon_types.len();
+        self.charge_started_at = None;
+        self.charge_released_at = None;
+        // reloads don't carry over to the next weapon
+        self.reload_started_at = None;
+    }
+
+    // adds the weapon if it isn't already held and switches to it either way
+    pub fn pick_up_weapon(self: &mut Weaponized, weapon_type: &str) {
+        let weapon_type = get_weapon(weapon_type).weapon_type;
+
+        let weapon_index = self
+            .weapon_types
+            .iter()
+            .position(|held_weapon_type| held_weapon_type == weapon_type);
+
+        if weapon_index.is_some() {
+            self.weapon_index = weapon_index.unwrap();
+            self.ammo[self.weapon_index] = get_full_ammo(weapon_type);
+        } else {
+            self.weapon_types.push(weapon_type.to_string());
+            self.ammo.push(get_full_ammo(weapon_type));
+            self.weapon_index = self.weapon_types.len() - 1;
+        }
+
+        self.charge_started_at = None;
+        self.charge_released_at = None;
+        self.reload_started_at = None;
+    }
+
+    pub fn is_reloading(self: &Weaponized) -> bool {
+        self.reload_started_at.is_some()
+    }
+
+    pub fn is_overheated(self: &Weaponized, now: f64) -> bool {
+        self.overheated_until.is_some() && now < self.overheated_until.unwrap()
+    }
+
+    pub fn can_fire(self: &Weaponized, now: f64) -> bool {
+        let weapon = self.get_weapon();
+
+        if weapon.magazine_size.is_some()
+            && (self.is_reloading() || self.ammo[self.weapon_index] == 0)
+        {
+            return false;
+        }
+
+        if weapon.heat_per_shot.is_some() && self.is_overheated(now) {
+            return false;
+        }
+
+        true
+    }
+
+    // uses up a shot's worth of ammo and / or heat
+    pub fn consume(self: &mut Weaponized, now: f64) {
+        let weapon = self.get_weapon();
+
+        if weapon.magazine_size.is_some() {
+            let weapon_index = self.weapon_index;
+            self.ammo[weapon_index] = self.ammo[weapon_index].saturating_sub(1);
+        }
+
+        if weapon.heat_per_shot.is_some() {
+            self.heat = (self.heat + weapon.heat_per_shot.unwrap()).min(1.0);
+
+            if self.heat >= 1.0 {
+                self.overheated_until = Some(now + WEAPON_OVERHEAT_SECONDS);
+            }
+        }
+    }
+
+    // cools the heat gauge and runs the reload for the weapon in hand
+    pub fn cool_and_reload(self: &mut Weaponized, now: f64, delta_seconds: f32) {
+        if !self.is_overheated(now) {
+            self.overheated_until = None;
+            self.heat = (self.heat - WEAPON_HEAT_COOLDOWN_PER_SECOND * delta_seconds).max(0.0);
+        }
+
+        let weapon = self.get_weapon();
+        if weapon.magazine_size.is_none() {
+            return;
+        }
+
+        if self.reload_started_at.is_none() {
+            if self.ammo[self.weapon_index] == 0 {
+                self.reload_started_at = Some(now);
+            }
+
+            return;
+        }
+
+        if now - self.reload_started_at.unwrap() >= weapon.reload_seconds {
+            let weapon_index = self.weapon_index;
+            self.ammo[weapon_index] = weapon.magazine_size.unwrap();
+            self.reload_started_at = None;
+        }
+    }
+
+    pub fn get_reload_progress(self: &Weaponized, now: f64) -> Option<f32> {
+        if self.reload_started_at.is_none() {
+            return None;
+        }
+
+        let weapon = self.get_weapon();
+
+        Some(
+            ((now - self.reload_started_at.unwrap()) / weapon.reload_seconds).clamp(0.0, 1.0)
+                as f32,
+        )
+    }
+
     pub fn fire(self: &Weaponized, fire_event_writer: &mut EventWriter<'_, FireEvent>) {
         fire_event_writer.send(FireEvent {
             weapon_uuid: self.weapon_uuid,
+            is_released: false,
+        });
+    }
+
+    pub fn release(self: &Weaponized, fire_event_writer: &mut EventWriter<'_, FireEvent>) {
+        fire_event_writer.send(FireEvent {
+            weapon_uuid: self.weapon_uuid,
+            is_released: true,
         });
     }
 }
 
+fn is_ready_to_fire(weaponized: &Weaponized, now: f64, fire_rate_multiplier: f32) -> bool {
+    let weapon = weaponized.get_weapon();
+
+    now - weaponized.last_fired_at >= weapon.fire_rate_seconds / fire_rate_multiplier as f64
+        && weaponized.can_fire(now)
+}
+
+fn fire_projectiles(
+    player: &Player,
+    weaponized: &mut Weaponized,
+    transform: &Transform,
+    charge: f32,
+    now: f64,
+    spawn_event_writer: &mut EventWriter<SpawnEvent>,
+) {
+    let weapon = weaponized.get_weapon();
+
+    let mut direction = 1.0;
+    if weapon.is_dropped {
+        direction = -1.0;
+    }
+
+    let projectile_offset = Vec3::new(
+        0.0,
+        (((MATERIAL_SCALE / 2.0) * PLAYER_HEIGHT_MULTIPLIER) + 10.0) * direction,
+        0.0,
+    );
+
+    for i in 0..weapon.projectile_count {
+        let mut spread = 0.0;
+        if weapon.projectile_count > 1 {
+            spread = (i as f32 / (weapon.projectile_count - 1) as f32) - 0.5;
+        }
+
+        let rotation = transform.rotation
+            * Quat::from_rotation_z(f32::to_radians(spread * weapon.spread_degrees));
+
+        let mut projectile_transform = *transform;
+        projectile_transform.rotation = rotation;
+        projectile_transform.translation += rotation.mul_vec3(projectile_offset);
+
+        let mut projectile_velocity = Velocity::default();
+        projectile_velocity.linvel = rotation
+            .mul_vec3(Vec3::new(
+                ZERO,
+                weapon.projectile_speed * weapon.get_charge_multiplier(charge),
+                0.0,
+            ))
+            .truncate();
+
+        spawn_event_writer.send(SpawnEvent {
+            entity_uuid: Uuid::new_v4(),
+            entity_type: "projectile".to_string(),
+            transform: Some(SerializableTransform::from_transform(projectile_transform)),
+            velocity: Some(SerializableVelocity::from_velocity(projectile_velocity)),
+            color: Some(weapon.color),
+            weapon_uuid: Some(weaponized.weapon_uuid),
+            player_uuid: Some(player.player_uuid),
+            weapon_type: Some(weapon.weapon_type.to_string()),
+            charge: Some(charge),
+            ..Default::default()
+        });
+    }
+
+    weaponized.last_fired_at = now;
+    weaponized.consume(now);
+}
+
 pub fn handle_fire_event(
     mut fire_event_reader: EventReader<FireEvent>,
     time: Res<Time>,
-    mut weapon_query: Query<(&mut Weaponized, &Transform)>,
+    mut weapon_query: Query<(&Player, &mut Weaponized, &Transform)>,
+    effect_query: Query<&Effect>,
     mut spawn_event_writer: EventWriter<SpawnEvent>,
 ) {
+    let now = time.elapsed_seconds_f64();
+
     for fire_event in fire_event_reader.read() {
-        for (mut weapon, transform) in weapon_query.iter_mut() {
-            if weapon.weapon_uuid != fire_event.weapon_uuid {
+        for (player, mut weaponized, transform) in weapon_query.iter_mut() {
+            if weaponized.weapon_uuid != fire_event.weapon_uuid {
                 continue;
             }
 
-            if time.elapsed_seconds_f64() - weapon.last_fired_at < WEAPON_FIRE_RATE_SECONDS {
+            let effect_modifiers = get_effect_modifiers(effect_query.iter(), player.player_uuid);
+            if effect_modifiers.is_stunned {
                 continue;
             }
 
-            let projectile_offset = Vec3::new(
-                0.0,
-                ((MATERIAL_SCALE / 2.0) * PLAYER_HEIGHT_MULTIPLIER) + 10.0,
-                0.0,
+            let weapon = weaponized.get_weapon();
+
+            // charged shots go out once they're let go of (below), whenever the weapon is ready
+            if weapon.charge_seconds.is_some() {
+                if weaponized.charge_released_at.is_some() {
+                    continue;
+                }
+
+                if !fire_event.is_released {
+                    if weaponized.charge_started_at.is_none() {
+                        weaponized.charge_started_at = Some(now);
+                    }
+                } else if weaponized.charge_started_at.is_some() {
+                    weaponized.charge_released_at = Some(now);
+                }
+
+                continue;
+            }
+
+            if fire_event.is_released
+                || !is_ready_to_fire(&weaponized, now, effect_modifiers.fire_rate_multiplier)
+            {
+                continue;
+            }
+
+            fire_projectiles(
+                player,
+                &mut weaponized,
+                transform,
+                1.0,
+                now,
+                &mut spawn_event_writer,
             );
+        }
+    }
 
-            let rotated_projected_offset = transform.rotation.mul_vec3(projectile_offset);
-            let mut projectile_transform = *transform;
-            projectile_transform.translation += rotated_projected_offset;
-
-            let mut projectile_velocity = Velocity::default();
-            projectile_velocity.linvel = transform
-                .rotation
-                .mul_vec3(Vec3::new(ZERO, PROJECTILE_LINEAR_VELOCITY, 0.0))
-                .truncate();
-
-            spawn_event_writer.send(SpawnEvent {
-                entity_uuid: Uuid::new_v4(),
-                entity_type: "projectile".to_string(),
-                transform: Some(SerializableTransform::from_transform(projectile_transform)),
-                velocity: Some(SerializableVelocity::from_velocity(projectile_velocity)),
-                color: Some(Color::srgb(0.0, 1.0, 1.0)),
-            });
-
-            weapon.last_fired_at = time.elapsed_seconds_f64();
+    for (player, mut weaponized, transform) in weapon_query.iter_mut() {
+        if weaponized.charge_released_at.is_none() || weaponized.charge_started_at.is_none() {
+            continue;
         }
+
+        let effect_modifiers = get_effect_modifiers(effect_query.iter(), player.player_uuid);
+        if effect_modifiers.is_stunned
+            || !is_ready_to_fire(&weaponized, now, effect_modifiers.fire_rate_multiplier)
+        {
+            continue;
+        }
+
+        // as charged as it was when it was let go of, however long it had to wait
+        let weapon = weaponized.get_weapon();
+        let charge = ((weaponized.charge_released_at.unwrap()
+            - weaponized.charge_started_at.unwrap())
+            / weapon.charge_seconds.unwrap_or(1.0))
+        .min(1.0) as f32;
+
+        weaponized.charge_started_at = None;
+        weaponized.charge_released_at = None;
+
+        fire_projectiles(
+            player,
+            &mut weaponized,
+            transform,
+            charge,
+            now,
+            &mut spawn_event_writer,
+        );
     }
 }
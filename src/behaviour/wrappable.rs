@@ -0,0 +1,80 @@
+use bevy::math::Vec2;
+use bevy::prelude::{Commands, Component, DespawnRecursiveExt, Entity, Mut, Transform};
+
+use crate::constants::{GHOST_MARGIN_MULTIPLIER, HALF};
+
+// a copy of something near an edge, on the other side of the arena; drawn on the client and
+// collided with on the server, so things don't just pop from one side to the other
+#[derive(Debug, Clone, Component)]
+pub struct Ghost {
+    pub source: Entity,
+    pub offset: Vec2,
+}
+
+fn get_wrap_offset(value: f32, extent: f32, margin: f32) -> Option<f32> {
+    if value > extent - margin {
+        return Some(-extent * 2.0);
+    }
+
+    if value < -extent + margin {
+        return Some(extent * 2.0);
+    }
+
+    None
+}
+
+// where the ghosts need to be for something at this transform (none if it's clear of the edges of
+// an arena this size)
+pub fn get_wrap_offsets(transform: &Transform, arena_size: Vec2) -> Vec<Vec2> {
+    let extents = arena_size * HALF;
+    let margin = transform.scale.truncate().max_element() * GHOST_MARGIN_MULTIPLIER;
+
+    let x = get_wrap_offset(transform.translation.x, extents.x, margin);
+    let y = get_wrap_offset(transform.translation.y, extents.y, margin);
+
+    let mut offsets = vec![];
+
+    if x.is_some() {
+        offsets.push(Vec2::new(x.unwrap(), 0.0));
+    }
+
+    if y.is_some() {
+        offsets.push(Vec2::new(0.0, y.unwrap()));
+    }
+
+    // the corners need a third one
+    if x.is_some() && y.is_some() {
+        offsets.push(Vec2::new(x.unwrap(), y.unwrap()));
+    }
+
+    offsets
+}
+
+// moves the ghosts that are still needed and despawns the rest; whatever's left in needed (source,
+// offset, transform) is for the caller to spawn
+pub fn update_ghosts<'a>(
+    needed: &mut Vec<(Entity, Vec2, Transform)>,
+    ghosts: impl Iterator<Item = (Entity, &'a Ghost, Mut<'a, Transform>)>,
+    commands: &mut Commands,
+) {
+    for (entity, ghost, mut transform) in ghosts {
+        let index = needed
+            .iter()
+            .position(|(source, offset, _)| *source == ghost.source && *offset == ghost.offset);
+
+        if index.is_none() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let (_, _, ghost_transform) = needed.remove(index.unwrap());
+        *transform = ghost_transform;
+    }
+}
+
+pub fn get_ghost_transform(transform: &Transform, offset: Vec2) -> Transform {
+    let mut ghost_transform = *transform;
+    ghost_transform.translation += offset.extend(0.0);
+
+    ghost_transform
+}
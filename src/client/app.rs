@@ -1,18 +1,56 @@
 use bevy::log::trace;
-use bevy::prelude::{App, FixedUpdate, Startup};
+use bevy::math::Vec2;
+use bevy::prelude::{App, FixedUpdate, IntoSystemConfigs, OnEnter, OnExit, Startup, Update};
 
 use crate::base::app::{
     get_base_app, AfterNetworkTransition1, AfterNetworkTransition2, AfterNetworkTransition3,
     NetworkTransition,
 };
+use crate::client::camera::{
+    handle_camera, handle_camera_toggle, handle_collision_event_for_camera, CameraFollow,
+};
+use crate::client::controls::{
+    handle_controls_screen_button, handle_controls_screen_listen, handle_controls_screen_toggle,
+    handle_controls_screen_ui, load_controls, ControlsScreen,
+};
+use crate::client::effect::{handle_effect_event, handle_effect_visual};
+use crate::client::game_mode::{
+    handle_flag_visual, handle_game_mode_event, handle_zone_visual, GameModeStatus,
+};
+use crate::client::ghost::handle_ghost_visual;
+use crate::client::hud::{
+    handle_hud_connection_status, handle_hud_health_bar, handle_hud_kill_feed,
+    handle_hud_objective_status, handle_hud_results_enter, handle_hud_results_exit,
+    handle_hud_round_status, handle_hud_scoreboard, handle_hud_scoreboard_toggle, handle_hud_setup,
+    handle_hud_weapon_status, handle_kill_event, KillFeed,
+};
 use crate::client::input::{
-    handle_input_event, handle_input_from_button, handle_input_from_keyboard, ButtonState,
+    handle_input_event, handle_input_from_gamepad, handle_input_from_keyboard, GamepadState,
+    TouchState,
+};
+use crate::client::label::handle_player_label;
+use crate::client::lobby::{
+    get_lobby, handle_lobby_button, handle_lobby_hello, handle_lobby_keyboard, handle_lobby_setup,
+    handle_lobby_ui,
 };
+use crate::client::minimap::{handle_minimap, handle_minimap_setup};
 use crate::client::moveable::handle_update_for_moveable;
-use crate::client::network::handle_websocket_client;
+use crate::client::network::{handle_close_event, handle_open_event, handle_websocket_client};
+use crate::client::ping::{handle_ping, handle_pong_event};
+use crate::client::round::handle_round_event;
+use crate::client::scoreboard::handle_scoreboard_event;
 use crate::client::setup::handle_setup;
+use crate::client::ship::{handle_ship_flame, handle_ship_puff, handle_ship_tint};
+use crate::client::touch::{handle_touch, handle_touch_setup, handle_touch_ui, TouchControls};
 use crate::client::update::handle_update_event;
+use crate::client::vfx::{
+    handle_collision_event_for_vfx, handle_kill_event_for_vfx, handle_particle,
+    handle_spawn_event_for_vfx, handle_thruster_trail, handle_vfx_setup,
+};
+use crate::client::viewport::{handle_viewport, handle_viewport_setup};
+use crate::client::weapon::{handle_weapon_event, WeaponStatus};
 use crate::client::websocket::get_websocket_client;
+use crate::identity::round::MatchState;
 
 pub fn get_app_for_client() -> App {
     let mut app = get_base_app();
@@ -24,12 +62,40 @@ pub fn get_app_for_client() -> App {
         web_socket_client
     );
 
-    app.insert_resource(ButtonState {
-        is_bottom_left_pressed: false,
-        is_bottom_right_pressed: false,
+    app.insert_resource(load_controls());
+
+    app.insert_resource(ControlsScreen {
+        is_open: false,
+        listening_action: None,
+    });
+
+    app.insert_resource(GamepadState::default());
+
+    app.insert_resource(TouchState::default());
+
+    app.insert_resource(TouchControls::default());
+
+    app.insert_resource(KillFeed { entries: vec![] });
+
+    app.insert_resource(get_lobby());
+
+    app.insert_resource(WeaponStatus { weapon: None });
+
+    app.insert_resource(GameModeStatus { game_mode: None });
+
+    app.insert_resource(CameraFollow {
+        is_following: None,
+        focus: Vec2::ZERO,
+        trauma: 0.0,
     });
 
     app.add_systems(Startup, handle_setup);
+    app.add_systems(Startup, handle_hud_setup);
+    app.add_systems(Startup, handle_lobby_setup);
+    app.add_systems(Startup, handle_vfx_setup);
+    app.add_systems(Startup, handle_minimap_setup);
+    app.add_systems(Startup, handle_viewport_setup);
+    app.add_systems(Startup, handle_touch_setup);
 
     // the client side implementation of the WebSocket
     app.insert_non_send_resource(web_socket_client);
@@ -38,17 +104,88 @@ pub fn get_app_for_client() -> App {
     app.add_systems(NetworkTransition, handle_websocket_client);
 
     // handlers to wire game update event into game state
+    app.add_systems(AfterNetworkTransition1, handle_open_event);
+    app.add_systems(AfterNetworkTransition1, handle_close_event);
     app.add_systems(AfterNetworkTransition1, handle_update_event);
+    app.add_systems(AfterNetworkTransition1, handle_pong_event);
+    app.add_systems(AfterNetworkTransition1, handle_scoreboard_event);
+    app.add_systems(AfterNetworkTransition1, handle_kill_event);
+    app.add_systems(AfterNetworkTransition1, handle_round_event);
+    app.add_systems(AfterNetworkTransition1, handle_weapon_event);
+    app.add_systems(AfterNetworkTransition1, handle_game_mode_event);
+    app.add_systems(AfterNetworkTransition1, handle_effect_event);
 
     // handler to wire raw input event into game input event
-    app.add_systems(AfterNetworkTransition2, handle_input_from_keyboard);
-    app.add_systems(AfterNetworkTransition2, handle_input_from_button);
+    app.add_systems(AfterNetworkTransition2, handle_input_from_gamepad);
+    app.add_systems(AfterNetworkTransition2, handle_touch);
+    app.add_systems(
+        AfterNetworkTransition2,
+        handle_input_from_keyboard
+            .after(handle_input_from_gamepad)
+            .after(handle_touch),
+    );
 
     // handler to wire game input event into network input event
     app.add_systems(AfterNetworkTransition3, handle_input_event);
 
+    // handlers to turn replicated game events into (client-only) visual effects
+    app.add_systems(AfterNetworkTransition3, handle_collision_event_for_vfx);
+    app.add_systems(AfterNetworkTransition3, handle_kill_event_for_vfx);
+    app.add_systems(AfterNetworkTransition3, handle_spawn_event_for_vfx);
+    app.add_systems(AfterNetworkTransition3, handle_collision_event_for_camera);
+
     // handlers to calculate game state per time step
     app.add_systems(FixedUpdate, handle_update_for_moveable);
+    app.add_systems(FixedUpdate, handle_ping);
+    app.add_systems(FixedUpdate, handle_particle);
+    app.add_systems(FixedUpdate, handle_thruster_trail);
+    app.add_systems(FixedUpdate, handle_ship_puff);
+
+    // handlers for the pre-join lobby
+    app.add_systems(Update, handle_lobby_keyboard);
+    app.add_systems(Update, handle_lobby_button);
+    app.add_systems(Update, handle_lobby_ui);
+    app.add_systems(Update, handle_lobby_hello);
+
+    // handlers for the controls screen
+    app.add_systems(Update, handle_controls_screen_toggle);
+    app.add_systems(Update, handle_controls_screen_button);
+    app.add_systems(
+        Update,
+        handle_controls_screen_listen
+            .after(handle_controls_screen_toggle)
+            .after(handle_controls_screen_button),
+    );
+    app.add_systems(
+        Update,
+        handle_controls_screen_ui.after(handle_controls_screen_listen),
+    );
+
+    // handlers to render game state into the hud
+    app.add_systems(Update, handle_hud_scoreboard_toggle);
+    app.add_systems(Update, handle_hud_scoreboard);
+    app.add_systems(Update, handle_hud_health_bar);
+    app.add_systems(Update, handle_hud_weapon_status);
+    app.add_systems(Update, handle_hud_connection_status);
+    app.add_systems(Update, handle_hud_kill_feed);
+    app.add_systems(Update, handle_hud_round_status);
+    app.add_systems(Update, handle_hud_objective_status);
+    app.add_systems(Update, handle_player_label);
+    app.add_systems(Update, handle_effect_visual);
+    app.add_systems(Update, handle_flag_visual);
+    app.add_systems(Update, handle_zone_visual);
+    app.add_systems(Update, handle_ghost_visual);
+    app.add_systems(Update, handle_ship_flame);
+    app.add_systems(Update, handle_ship_tint);
+
+    // handlers to point the camera (and fit it to the window) and draw the minimap
+    app.add_systems(Update, handle_viewport);
+    app.add_systems(Update, handle_camera_toggle);
+    app.add_systems(Update, handle_camera.after(handle_camera_toggle));
+    app.add_systems(Update, handle_minimap.after(handle_camera));
+    app.add_systems(Update, handle_touch_ui);
+    app.add_systems(OnEnter(MatchState::Results), handle_hud_results_enter);
+    app.add_systems(OnExit(MatchState::Results), handle_hud_results_exit);
 
     trace!("client.get_app(); returning app={:?}", app);
 
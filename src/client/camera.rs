@@ -0,0 +1,158 @@
+use bevy::input::gamepad::{GamepadButton, Gamepads};
+use bevy::input::ButtonInput;
+use bevy::math::Vec2;
+use bevy::prelude::{
+    EventReader, KeyCode, OrthographicProjection, Query, Res, ResMut, Resource, Time, Transform,
+    With, Without,
+};
+use rand::{thread_rng, Rng};
+
+use crate::base::setup::MainCamera;
+use crate::behaviour::collideable::{CollisionEvent, CollisionKind};
+use crate::client::controls::{is_action_just_pressed, Controls};
+use crate::client::lobby::Lobby;
+use crate::constants::{
+    CAMERA_DEADZONE, CAMERA_SHAKE_DECAY, CAMERA_SHAKE_OFFSET_MAX, CAMERA_SHAKE_TRAUMA,
+    CAMERA_SMOOTHING, HALF, ZERO,
+};
+use crate::identity::game::Game;
+use crate::identity::player::Player;
+
+#[derive(Debug, Clone, Resource)]
+pub struct CameraFollow {
+    // None to follow only when the arena doesn't fit in the view
+    pub is_following: Option<bool>,
+    // where the camera is pointed, before any shake
+    pub focus: Vec2,
+    // 0.0 to 1.0; the shake is the square of it, so small knocks stay subtle
+    pub trauma: f32,
+}
+
+fn is_following(camera_follow: &CameraFollow, game: &Game, view_size: Vec2) -> bool {
+    if camera_follow.is_following.is_some() {
+        return camera_follow.is_following.unwrap();
+    }
+
+    game.arena_size.x > view_size.x || game.arena_size.y > view_size.y
+}
+
+pub fn handle_camera_toggle(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_button_input: Res<ButtonInput<GamepadButton>>,
+    controls: Res<Controls>,
+    lobby: Res<Lobby>,
+    game: Res<Game>,
+    mut camera_follow: ResMut<CameraFollow>,
+    projection_query: Query<&OrthographicProjection, With<MainCamera>>,
+) {
+    if !lobby.is_submitted {
+        return;
+    }
+
+    if !is_action_just_pressed(
+        &controls,
+        "camera",
+        &keyboard_input,
+        &gamepads,
+        &gamepad_button_input,
+    ) {
+        return;
+    }
+
+    for projection in projection_query.iter() {
+        let was_following = is_following(&camera_follow, &game, projection.area.size());
+
+        camera_follow.is_following = Some(!was_following);
+    }
+}
+
+// a little shake whenever the local player runs into (or is hit by) something
+pub fn handle_collision_event_for_camera(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    game: Res<Game>,
+    mut camera_follow: ResMut<CameraFollow>,
+) {
+    if game.local_player_uuid.is_none() {
+        collision_event_reader.clear();
+        return;
+    }
+
+    let local_player_uuid = game.local_player_uuid.unwrap();
+
+    for collision in collision_event_reader.read() {
+        if collision.kind != CollisionKind::Contact {
+            continue;
+        }
+
+        if collision.collider_a.entity_uuid != local_player_uuid
+            && collision.collider_b.entity_uuid != local_player_uuid
+        {
+            continue;
+        }
+
+        camera_follow.trauma = (camera_follow.trauma + CAMERA_SHAKE_TRAUMA).min(1.0);
+    }
+}
+
+// eases towards the local player once they leave the deadzone (or back to the middle if not
+// following), staying inside solid edges, then adds the shake on top
+pub fn handle_camera(
+    time: Res<Time>,
+    game: Res<Game>,
+    mut camera_follow: ResMut<CameraFollow>,
+    player_query: Query<(&Player, &Transform), Without<MainCamera>>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    let delta = time.delta_seconds();
+
+    camera_follow.trauma = (camera_follow.trauma - CAMERA_SHAKE_DECAY * delta).max(ZERO);
+
+    for (mut camera_transform, projection) in camera_query.iter_mut() {
+        let view_size = projection.area.size();
+
+        let mut target = Vec2::ZERO;
+
+        let local_player = player_query
+            .iter()
+            .find(|(player, _)| player.is_local_player);
+
+        if is_following(&camera_follow, &game, view_size) && local_player.is_some() {
+            let (_, transform) = local_player.unwrap();
+            let translation = transform.translation.truncate();
+            let offset = translation - camera_follow.focus;
+
+            // they've gone over a wrapping edge; no point panning across the whole arena
+            if game.is_wrapping
+                && (offset.x.abs() > game.arena_size.x * HALF
+                    || offset.y.abs() > game.arena_size.y * HALF)
+            {
+                camera_follow.focus = translation;
+            }
+
+            target = translation
+                - (translation - camera_follow.focus).clamp(-CAMERA_DEADZONE, CAMERA_DEADZONE);
+
+            if !game.is_wrapping {
+                let limit = ((game.arena_size - view_size) * HALF).max(Vec2::ZERO);
+
+                target = target.clamp(-limit, limit);
+            }
+        }
+
+        camera_follow.focus = camera_follow
+            .focus
+            .lerp(target, 1.0 - (-CAMERA_SMOOTHING * delta).exp());
+
+        let mut rng = thread_rng();
+
+        let shake = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+            * camera_follow.trauma.powi(2)
+            * CAMERA_SHAKE_OFFSET_MAX;
+
+        let translation = camera_follow.focus + shake;
+
+        camera_transform.translation.x = translation.x;
+        camera_transform.translation.y = translation.y;
+    }
+}
@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+
+use bevy::input::gamepad::{GamepadButton, GamepadButtonType, Gamepads};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonInput;
+use bevy::log::warn;
+use bevy::prelude::{
+    default, AlignItems, BuildChildren, Button, ButtonBundle, Changed, ChildBuilder, Color,
+    Commands, Component, DespawnRecursiveExt, DetectChanges, Entity, EventReader, FlexDirection,
+    Interaction, JustifyContent, KeyCode, NodeBundle, PositionType, Query, Res, ResMut, Resource,
+    Style, TextBundle, TextStyle, UiRect, Val, With,
+};
+use serde::{Deserialize, Serialize};
+use web_sys::{window, Storage};
+
+use crate::constants::{
+    CAMERA_FOLLOW_KEY, CONTROLS_ACTIONS, CONTROLS_ACTION_WIDTH, CONTROLS_BINDINGS_WIDTH,
+    CONTROLS_LISTENING_COLOR, CONTROLS_SCREEN_KEY, CONTROLS_STORAGE_KEY, HUD_BACKGROUND_COLOR,
+    HUD_FONT_SIZE, HUD_MARGIN, HUD_SCOREBOARD_GAMEPAD_BUTTON, HUD_SCOREBOARD_KEY, HUD_TEXT_COLOR,
+    PLAYER_BACKWARD_GAMEPAD_BUTTON, PLAYER_BACKWARD_KEYS, PLAYER_FIRE_GAMEPAD_BUTTON,
+    PLAYER_FIRE_KEYS, PLAYER_FORWARD_GAMEPAD_BUTTON, PLAYER_FORWARD_KEYS,
+    PLAYER_LEFT_GAMEPAD_BUTTON, PLAYER_LEFT_KEYS, PLAYER_RIGHT_GAMEPAD_BUTTON, PLAYER_RIGHT_KEYS,
+    PLAYER_SWITCH_WEAPON_GAMEPAD_BUTTON, PLAYER_SWITCH_WEAPON_KEYS, UI_BUTTON_NORMAL,
+    UI_BUTTON_WIDTH,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    GamepadButton(GamepadButtonType),
+}
+
+// each action (see CONTROLS_ACTIONS) to whatever keys / buttons trigger it; saved in the
+// browser's local storage whenever it changes
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct Controls {
+    pub bindings: HashMap<String, Vec<Binding>>,
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct ControlsScreen {
+    pub is_open: bool,
+    // waiting for a key / button to add to this action
+    pub listening_action: Option<String>,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct ControlsScreenRoot {}
+
+#[derive(Debug, Clone, Component)]
+pub struct ControlsAddButton {
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct ControlsClearButton {
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct ControlsResetButton {}
+
+#[derive(Debug, Clone, Component)]
+pub struct ControlsCloseButton {}
+
+impl Controls {
+    pub fn get_keys(self: &Controls, action: &str) -> Vec<KeyCode> {
+        let mut keys = vec![];
+
+        for binding in self.bindings.get(action).unwrap_or(&vec![]) {
+            if let Binding::Key(key_code) = binding {
+                keys.push(*key_code);
+            }
+        }
+
+        keys
+    }
+
+    pub fn get_gamepad_buttons(self: &Controls, action: &str) -> Vec<GamepadButtonType> {
+        let mut buttons = vec![];
+
+        for binding in self.bindings.get(action).unwrap_or(&vec![]) {
+            if let Binding::GamepadButton(button_type) = binding {
+                buttons.push(*button_type);
+            }
+        }
+
+        buttons
+    }
+
+    pub fn is_key_pressed(
+        self: &Controls,
+        action: &str,
+        keyboard_input: &ButtonInput<KeyCode>,
+    ) -> bool {
+        keyboard_input.any_pressed(self.get_keys(action))
+    }
+
+    // a binding only does one thing, so it's taken off anything else it was bound to
+    pub fn add_binding(self: &mut Controls, action: &str, binding: Binding) {
+        for bindings in self.bindings.values_mut() {
+            bindings.retain(|other| *other != binding);
+        }
+
+        self.bindings
+            .entry(action.to_string())
+            .or_default()
+            .push(binding);
+    }
+}
+
+// for the actions that happen once per press, rather than for as long as they're held
+pub fn is_action_just_pressed(
+    controls: &Controls,
+    action: &str,
+    keyboard_input: &ButtonInput<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_button_input: &ButtonInput<GamepadButton>,
+) -> bool {
+    if keyboard_input.any_just_pressed(controls.get_keys(action)) {
+        return true;
+    }
+
+    for gamepad in gamepads.iter() {
+        for button_type in controls.get_gamepad_buttons(action) {
+            if gamepad_button_input.just_pressed(GamepadButton::new(gamepad, button_type)) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+pub fn get_default_controls() -> Controls {
+    let mut bindings = HashMap::new();
+
+    let mut bind = |action: &str, keys: &[KeyCode], buttons: &[GamepadButtonType]| {
+        bindings.insert(
+            action.to_string(),
+            keys.iter()
+                .map(|key_code| Binding::Key(*key_code))
+                .chain(
+                    buttons
+                        .iter()
+                        .map(|button_type| Binding::GamepadButton(*button_type)),
+                )
+                .collect(),
+        );
+    };
+
+    bind(
+        "forward",
+        &PLAYER_FORWARD_KEYS,
+        &[PLAYER_FORWARD_GAMEPAD_BUTTON],
+    );
+    bind(
+        "backward",
+        &PLAYER_BACKWARD_KEYS,
+        &[PLAYER_BACKWARD_GAMEPAD_BUTTON],
+    );
+    bind("left", &PLAYER_LEFT_KEYS, &[PLAYER_LEFT_GAMEPAD_BUTTON]);
+    bind("right", &PLAYER_RIGHT_KEYS, &[PLAYER_RIGHT_GAMEPAD_BUTTON]);
+    bind("fire", &PLAYER_FIRE_KEYS, &[PLAYER_FIRE_GAMEPAD_BUTTON]);
+    bind(
+        "switch_weapon",
+        &PLAYER_SWITCH_WEAPON_KEYS,
+        &[PLAYER_SWITCH_WEAPON_GAMEPAD_BUTTON],
+    );
+    bind(
+        "scoreboard",
+        &[HUD_SCOREBOARD_KEY],
+        &[HUD_SCOREBOARD_GAMEPAD_BUTTON],
+    );
+    bind("camera", &[CAMERA_FOLLOW_KEY], &[]);
+
+    Controls { bindings }
+}
+
+// None if there's no window (or the browser won't let us have it)
+fn get_local_storage() -> Option<Storage> {
+    window()?.local_storage().ok()?
+}
+
+// whatever was saved last time, with anything missing (e.g. actions added since) left as the
+// defaults
+pub fn load_controls() -> Controls {
+    let mut controls = get_default_controls();
+
+    let storage = get_local_storage();
+    if storage.is_none() {
+        return controls;
+    }
+
+    let item = storage.unwrap().get_item(CONTROLS_STORAGE_KEY);
+    if item.is_err() || item.as_ref().unwrap().is_none() {
+        return controls;
+    }
+
+    let saved = serde_json::from_str::<Controls>(item.unwrap().unwrap().as_str());
+    if saved.is_err() {
+        warn!(
+            "load_controls; couldn't parse saved controls: {:?}",
+            saved.err()
+        );
+        return controls;
+    }
+
+    for (action, bindings) in saved.unwrap().bindings {
+        if CONTROLS_ACTIONS.contains(&action.as_str()) {
+            controls.bindings.insert(action, bindings);
+        }
+    }
+
+    controls
+}
+
+pub fn save_controls(controls: &Controls) {
+    let storage = get_local_storage();
+    if storage.is_none() {
+        return;
+    }
+
+    let result = storage.unwrap().set_item(
+        CONTROLS_STORAGE_KEY,
+        serde_json::to_string(controls).unwrap().as_str(),
+    );
+
+    if result.is_err() {
+        warn!("save_controls; couldn't save controls: {:?}", result.err());
+    }
+}
+
+fn get_binding_name(binding: &Binding) -> String {
+    match binding {
+        Binding::Key(key_code) => format!("{:?}", key_code),
+        Binding::GamepadButton(button_type) => format!("pad {:?}", button_type),
+    }
+}
+
+fn get_text_style(color: Color) -> TextStyle {
+    TextStyle {
+        font_size: HUD_FONT_SIZE,
+        color,
+        ..default()
+    }
+}
+
+fn spawn_button(parent: &mut ChildBuilder, label: &str, component: impl Component) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(UI_BUTTON_WIDTH / 2.0),
+                    padding: UiRect::all(Val::Px(HUD_MARGIN / 4.0)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: UI_BUTTON_NORMAL.into(),
+                ..default()
+            },
+            component,
+        ))
+        .with_children(|button| {
+            button.spawn(TextBundle::from_section(
+                label,
+                get_text_style(HUD_TEXT_COLOR),
+            ));
+        });
+}
+
+pub fn handle_controls_screen_toggle(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut controls_screen: ResMut<ControlsScreen>,
+) {
+    if !keyboard_input.just_pressed(CONTROLS_SCREEN_KEY) {
+        return;
+    }
+
+    // the first press just stops listening, if it was
+    if controls_screen.listening_action.is_some() {
+        controls_screen.listening_action = None;
+        return;
+    }
+
+    controls_screen.is_open = !controls_screen.is_open;
+}
+
+pub fn handle_controls_screen_button(
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&ControlsAddButton>,
+            Option<&ControlsClearButton>,
+            Option<&ControlsResetButton>,
+            Option<&ControlsCloseButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut controls: ResMut<Controls>,
+    mut controls_screen: ResMut<ControlsScreen>,
+) {
+    for (interaction, add_button, clear_button, reset_button, close_button) in
+        interaction_query.iter()
+    {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        if add_button.is_some() {
+            controls_screen.listening_action = Some(add_button.unwrap().action.clone());
+        }
+
+        if clear_button.is_some() {
+            controls
+                .bindings
+                .insert(clear_button.unwrap().action.clone(), vec![]);
+            save_controls(&controls);
+        }
+
+        if reset_button.is_some() {
+            *controls = get_default_controls();
+            save_controls(&controls);
+        }
+
+        if close_button.is_some() {
+            controls_screen.is_open = false;
+            controls_screen.listening_action = None;
+        }
+    }
+}
+
+// the next key or gamepad button pressed is added to whatever action is listening
+pub fn handle_controls_screen_listen(
+    mut keyboard_input_reader: EventReader<KeyboardInput>,
+    gamepad_button_input: Res<ButtonInput<GamepadButton>>,
+    mut controls: ResMut<Controls>,
+    mut controls_screen: ResMut<ControlsScreen>,
+) {
+    let mut binding = None;
+
+    for keyboard_input in keyboard_input_reader.read() {
+        if !keyboard_input.state.is_pressed() || keyboard_input.key_code == CONTROLS_SCREEN_KEY {
+            continue;
+        }
+
+        binding = Some(Binding::Key(keyboard_input.key_code));
+    }
+
+    for gamepad_button in gamepad_button_input.get_just_pressed() {
+        binding = Some(Binding::GamepadButton(gamepad_button.button_type));
+    }
+
+    if controls_screen.listening_action.is_none() || binding.is_none() {
+        return;
+    }
+
+    let action = controls_screen.listening_action.clone().unwrap();
+
+    controls.add_binding(action.as_str(), binding.unwrap());
+    save_controls(&controls);
+
+    controls_screen.listening_action = None;
+}
+
+// rebuilt from scratch whenever anything on it changes
+pub fn handle_controls_screen_ui(
+    controls: Res<Controls>,
+    controls_screen: Res<ControlsScreen>,
+    root_query: Query<Entity, With<ControlsScreenRoot>>,
+    mut commands: Commands,
+) {
+    if !(controls.is_changed() || controls_screen.is_changed()) {
+        return;
+    }
+
+    for entity in root_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    if !controls_screen.is_open {
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Percent(15.0),
+                    top: Val::Percent(10.0),
+                    width: Val::Percent(70.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::all(Val::Px(HUD_MARGIN)),
+                    row_gap: Val::Px(HUD_MARGIN / 2.0),
+                    ..default()
+                },
+                background_color: HUD_BACKGROUND_COLOR.into(),
+                ..default()
+            },
+            ControlsScreenRoot {},
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "controls (esc to close)",
+                get_text_style(HUD_TEXT_COLOR),
+            ));
+
+            for action in CONTROLS_ACTIONS.iter() {
+                let is_listening = controls_screen.listening_action.is_some()
+                    && controls_screen.listening_action.as_ref().unwrap() == action;
+
+                let mut value = controls
+                    .bindings
+                    .get(*action)
+                    .unwrap_or(&vec![])
+                    .iter()
+                    .map(get_binding_name)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                let mut color = HUD_TEXT_COLOR;
+
+                if is_listening {
+                    value = "press a key or button...".to_string();
+                    color = CONTROLS_LISTENING_COLOR;
+                }
+
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(HUD_MARGIN),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(
+                            TextBundle::from_section(
+                                action.replace('_', " "),
+                                get_text_style(HUD_TEXT_COLOR),
+                            )
+                            .with_style(Style {
+                                width: Val::Px(CONTROLS_ACTION_WIDTH),
+                                ..default()
+                            }),
+                        );
+
+                        row.spawn(
+                            TextBundle::from_section(value, get_text_style(color)).with_style(
+                                Style {
+                                    width: Val::Px(CONTROLS_BINDINGS_WIDTH),
+                                    ..default()
+                                },
+                            ),
+                        );
+
+                        spawn_button(
+                            row,
+                            "add",
+                            ControlsAddButton {
+                                action: action.to_string(),
+                            },
+                        );
+
+                        spawn_button(
+                            row,
+                            "clear",
+                            ControlsClearButton {
+                                action: action.to_string(),
+                            },
+                        );
+                    });
+            }
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(HUD_MARGIN),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|row| {
+                    spawn_button(row, "reset", ControlsResetButton {});
+                    spawn_button(row, "close", ControlsCloseButton {});
+                });
+        });
+}
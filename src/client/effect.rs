@@ -0,0 +1,60 @@
+use bevy::prelude::{EventReader, Query, Transform, Vec3, Visibility, Without};
+
+use crate::constants::{MATERIAL_SCALE, PLAYER_HEIGHT_MULTIPLIER};
+use crate::identity::effect::{get_effect_rule, Effect};
+use crate::identity::player::Player;
+use crate::types::event::EffectEvent;
+
+pub fn handle_effect_event(
+    mut effect_event_reader: EventReader<EffectEvent>,
+    mut effect_query: Query<&mut Effect>,
+) {
+    for effect_event in effect_event_reader.read() {
+        for mut effect in effect_query.iter_mut() {
+            if effect.effect_uuid != effect_event.effect_uuid {
+                continue;
+            }
+
+            effect.magnitude = effect_event.magnitude;
+        }
+    }
+}
+
+// effects are separate entities (like labels) so they don't inherit the ship's rotation and scale
+pub fn handle_effect_visual(
+    player_query: Query<(&Player, &Transform)>,
+    mut effect_query: Query<(&Effect, &mut Transform, &mut Visibility), Without<Player>>,
+) {
+    for (effect, mut effect_transform, mut visibility) in effect_query.iter_mut() {
+        let player = player_query
+            .iter()
+            .find(|(player, _)| player.player_uuid == effect.player_uuid);
+
+        if player.is_none() {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let (_, transform) = player.unwrap();
+
+        // just behind the ship
+        effect_transform.translation = transform
+            .translation
+            .truncate()
+            .extend(transform.translation.z - 0.01);
+        *visibility = Visibility::Inherited;
+
+        // shields shrink back towards the ship as they soak up damage
+        let effect_rule = get_effect_rule(&effect.effect_type);
+        if effect.effect_type == "shield" && effect_rule.is_some() {
+            let effect_rule = effect_rule.unwrap();
+            let remaining = (effect.magnitude / effect_rule.magnitude).clamp(0.0, 1.0);
+
+            effect_transform.scale = Vec3::splat(
+                MATERIAL_SCALE
+                    * PLAYER_HEIGHT_MULTIPLIER
+                    * (1.0 + (effect_rule.radius_multiplier - 1.0) * remaining),
+            );
+        }
+    }
+}
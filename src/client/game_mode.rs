@@ -0,0 +1,93 @@
+use bevy::asset::{Assets, Handle};
+use bevy::color::Alpha;
+use bevy::prelude::{
+    ColorMaterial, DetectChanges, EventReader, Query, Res, ResMut, Resource, Transform, With,
+    Without,
+};
+
+use crate::constants::{BASE_ALPHA, ZONE_COLOR};
+use crate::identity::asset_cache::AssetCache;
+use crate::identity::flag::Flag;
+use crate::identity::player::Player;
+use crate::identity::team::get_team_color;
+use crate::identity::zone::Zone;
+use crate::types::event::GameModeEvent;
+
+// the objectives as last told by the server; None in modes without any
+#[derive(Debug, Clone, Resource)]
+pub struct GameModeStatus {
+    pub game_mode: Option<GameModeEvent>,
+}
+
+pub fn handle_game_mode_event(
+    mut game_mode_event_reader: EventReader<GameModeEvent>,
+    mut game_mode_status: ResMut<GameModeStatus>,
+) {
+    for game_mode_event in game_mode_event_reader.read() {
+        game_mode_status.game_mode = Some(game_mode_event.clone());
+    }
+}
+
+// carried flags stick to the carrier locally, so they don't lag behind at the network update rate
+pub fn handle_flag_visual(
+    game_mode_status: Res<GameModeStatus>,
+    player_query: Query<(&Player, &Transform)>,
+    mut flag_query: Query<(&Flag, &mut Transform), Without<Player>>,
+) {
+    if game_mode_status.game_mode.is_none() {
+        return;
+    }
+
+    let game_mode = game_mode_status.game_mode.as_ref().unwrap();
+
+    for (flag, mut transform) in flag_query.iter_mut() {
+        let flag_state = game_mode
+            .flags
+            .iter()
+            .find(|flag_state| flag_state.flag_uuid == flag.flag_uuid);
+
+        if flag_state.is_none() {
+            continue;
+        }
+
+        let flag_state = flag_state.unwrap();
+
+        let mut translation = flag_state.translation;
+
+        if flag_state.carrier_uuid.is_some() {
+            let carrier = player_query
+                .iter()
+                .find(|(player, _)| player.player_uuid == flag_state.carrier_uuid.unwrap());
+
+            if carrier.is_some() {
+                translation = carrier.unwrap().1.translation.truncate();
+            }
+        }
+
+        transform.translation = translation.extend(transform.translation.z);
+    }
+}
+
+// the zone takes on the colour of whoever has it to themselves
+pub fn handle_zone_visual(
+    game_mode_status: Res<GameModeStatus>,
+    mut zone_query: Query<&mut Handle<ColorMaterial>, With<Zone>>,
+    mut asset_cache: ResMut<AssetCache>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !game_mode_status.is_changed() || game_mode_status.game_mode.is_none() {
+        return;
+    }
+
+    let zone_team = game_mode_status.game_mode.as_ref().unwrap().zone_team;
+
+    let mut color = ZONE_COLOR;
+    if zone_team.is_some() {
+        color = get_team_color(zone_team.unwrap()).with_alpha(BASE_ALPHA);
+    }
+
+    // materials are shared, so it's swapped for another one rather than recoloured
+    for mut handle in zone_query.iter_mut() {
+        *handle = asset_cache.get_material(color, &mut materials);
+    }
+}
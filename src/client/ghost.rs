@@ -0,0 +1,67 @@
+use bevy::asset::Handle;
+use bevy::prelude::{default, Commands, Entity, Query, Res, Transform, With, Without};
+use bevy::sprite::{ColorMaterial, MaterialMesh2dBundle, Mesh2dHandle};
+
+use crate::behaviour::moveable::Moveable;
+use crate::behaviour::wrappable::{get_ghost_transform, get_wrap_offsets, update_ghosts, Ghost};
+use crate::identity::game::Game;
+
+// draws copies of anything hanging over an edge on the opposite side, so it slides across
+// instead of popping; the ghosts borrow the source's mesh and material (and keep up with it if the
+// material changes, e.g. ships darkening as they take damage)
+pub fn handle_ghost_visual(
+    mut commands: Commands,
+    game: Res<Game>,
+    source_query: Query<
+        (Entity, &Transform, &Mesh2dHandle, &Handle<ColorMaterial>),
+        (With<Moveable>, Without<Ghost>),
+    >,
+    mut ghost_query: Query<
+        (Entity, &Ghost, &mut Transform, &mut Handle<ColorMaterial>),
+        Without<Moveable>,
+    >,
+) {
+    let mut needed = vec![];
+
+    if game.is_wrapping {
+        for (entity, transform, _, _) in source_query.iter() {
+            for offset in get_wrap_offsets(transform, game.arena_size) {
+                needed.push((entity, offset, get_ghost_transform(transform, offset)));
+            }
+        }
+    }
+
+    for (_, ghost, _, mut material) in ghost_query.iter_mut() {
+        let source = source_query.get(ghost.source);
+        if source.is_err() {
+            continue;
+        }
+
+        let (_, _, _, source_material) = source.unwrap();
+        if *material != *source_material {
+            *material = source_material.clone();
+        }
+    }
+
+    update_ghosts(
+        &mut needed,
+        ghost_query
+            .iter_mut()
+            .map(|(entity, ghost, transform, _)| (entity, ghost, transform)),
+        &mut commands,
+    );
+
+    for (source, offset, transform) in needed {
+        let (_, _, mesh, material) = source_query.get(source).unwrap();
+
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform,
+                ..default()
+            },
+            Ghost { source, offset },
+        ));
+    }
+}
@@ -0,0 +1,658 @@
+use bevy::input::gamepad::{GamepadButton, Gamepads};
+use bevy::input::ButtonInput;
+use bevy::prelude::{
+    default, AlignItems, BackgroundColor, BuildChildren, ChildBuilder, Color, Commands, Component,
+    DespawnRecursiveExt, DetectChanges, Entity, EventReader, FlexDirection, JustifyText, KeyCode,
+    NodeBundle, PositionType, Query, Res, ResMut, Resource, Style, Text, TextBundle, TextStyle,
+    Time, UiRect, Val, Visibility, With,
+};
+use uuid::Uuid;
+
+use crate::client::controls::{is_action_just_pressed, Controls};
+use crate::client::game_mode::GameModeStatus;
+use crate::client::lobby::Lobby;
+use crate::client::weapon::WeaponStatus;
+use crate::constants::{
+    HUD_BACKGROUND_COLOR, HUD_CONNECTED_COLOR, HUD_DISCONNECTED_COLOR, HUD_FONT_SIZE,
+    HUD_HEALTH_BAR_COLOR, HUD_HEALTH_BAR_HEIGHT, HUD_HEALTH_BAR_WIDTH, HUD_HEAT_BAR_COLOR,
+    HUD_KILL_FEED_EXPIRY_SECONDS, HUD_KILL_FEED_LENGTH_MAX, HUD_MARGIN, HUD_OVERHEATED_COLOR,
+    HUD_SWATCH_SIZE, HUD_TEXT_COLOR,
+};
+use crate::identity::game::Game;
+use crate::identity::round::{MatchState, Round};
+use crate::identity::scoreboard::Scoreboard;
+use crate::identity::team::get_team_name;
+use crate::types::event::KillEvent;
+
+#[derive(Debug, Clone, Component)]
+pub struct HudScoreboard {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudScoreboardRow {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudHealthBar {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudWeaponStatus {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudWeaponBar {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudConnectionStatus {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudKillFeed {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudRoundStatus {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudKillFeedEntry {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudObjectiveStatus {}
+
+#[derive(Debug, Clone, Resource)]
+pub struct KillFeed {
+    // (client time received at, kill)
+    pub entries: Vec<(f64, KillEvent)>,
+}
+
+pub fn get_display_name(scoreboard: &Scoreboard, player_uuid: Uuid) -> String {
+    for entry in scoreboard.entries.iter() {
+        if entry.player_uuid == player_uuid {
+            return entry.name.clone();
+        }
+    }
+
+    // not on the scoreboard yet (or any more)
+    player_uuid.to_string()[..8].to_string()
+}
+
+fn get_text_style(color: Color) -> TextStyle {
+    TextStyle {
+        font_size: HUD_FONT_SIZE,
+        color,
+        ..default()
+    }
+}
+
+fn spawn_text(parent: &mut ChildBuilder, value: String, width: f32) {
+    parent.spawn(
+        TextBundle::from_section(value, get_text_style(HUD_TEXT_COLOR)).with_style(Style {
+            width: Val::Px(width),
+            ..default()
+        }),
+    );
+}
+
+pub fn handle_hud_setup(mut commands: Commands) {
+    // scoreboard, hidden until toggled
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(25.0),
+                top: Val::Percent(15.0),
+                width: Val::Percent(50.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(HUD_MARGIN)),
+                row_gap: Val::Px(HUD_MARGIN / 2.0),
+                ..default()
+            },
+            background_color: HUD_BACKGROUND_COLOR.into(),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        HudScoreboard {},
+    ));
+
+    // health bar for the local player
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(HUD_MARGIN),
+                top: Val::Px(HUD_MARGIN),
+                width: Val::Px(HUD_HEALTH_BAR_WIDTH),
+                height: Val::Px(HUD_HEALTH_BAR_HEIGHT),
+                ..default()
+            },
+            background_color: HUD_BACKGROUND_COLOR.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: HUD_HEALTH_BAR_COLOR.into(),
+                    ..default()
+                },
+                HudHealthBar {},
+            ));
+        });
+
+    // weapon in hand with its ammo / heat, under the health bar
+    commands.spawn((
+        TextBundle::from_section("".to_string(), get_text_style(HUD_TEXT_COLOR)).with_style(
+            Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(HUD_MARGIN),
+                top: Val::Px(HUD_MARGIN * 2.0 + HUD_HEALTH_BAR_HEIGHT),
+                ..default()
+            },
+        ),
+        HudWeaponStatus {},
+    ));
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(HUD_MARGIN),
+                top: Val::Px(HUD_MARGIN * 3.0 + HUD_HEALTH_BAR_HEIGHT + HUD_FONT_SIZE),
+                width: Val::Px(HUD_HEALTH_BAR_WIDTH),
+                height: Val::Px(HUD_HEALTH_BAR_HEIGHT / 2.0),
+                ..default()
+            },
+            background_color: HUD_BACKGROUND_COLOR.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    background_color: HUD_HEAT_BAR_COLOR.into(),
+                    ..default()
+                },
+                HudWeaponBar {},
+            ));
+        });
+
+    // connection status
+    commands.spawn((
+        TextBundle::from_section(
+            "connecting...".to_string(),
+            get_text_style(HUD_DISCONNECTED_COLOR),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            right: Val::Px(HUD_MARGIN),
+            top: Val::Px(HUD_MARGIN),
+            ..default()
+        }),
+        HudConnectionStatus {},
+    ));
+
+    // round status, along the top
+    commands.spawn((
+        TextBundle::from_section("".to_string(), get_text_style(HUD_TEXT_COLOR))
+            .with_text_justify(JustifyText::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(HUD_MARGIN),
+                ..default()
+            }),
+        HudRoundStatus {},
+    ));
+
+    // objective status, under the round status
+    commands.spawn((
+        TextBundle::from_section("".to_string(), get_text_style(HUD_TEXT_COLOR))
+            .with_text_justify(JustifyText::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(HUD_MARGIN * 2.0 + HUD_FONT_SIZE),
+                ..default()
+            }),
+        HudObjectiveStatus {},
+    ));
+
+    // kill feed, under the connection status
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(HUD_MARGIN),
+                top: Val::Px(HUD_MARGIN * 2.0 + HUD_FONT_SIZE),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexEnd,
+                ..default()
+            },
+            ..default()
+        },
+        HudKillFeed {},
+    ));
+}
+
+pub fn handle_hud_scoreboard_toggle(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_button_input: Res<ButtonInput<GamepadButton>>,
+    controls: Res<Controls>,
+    lobby: Res<Lobby>,
+    mut scoreboard_query: Query<&mut Visibility, With<HudScoreboard>>,
+) {
+    if !lobby.is_submitted {
+        return;
+    }
+
+    if !is_action_just_pressed(
+        &controls,
+        "scoreboard",
+        &keyboard_input,
+        &gamepads,
+        &gamepad_button_input,
+    ) {
+        return;
+    }
+
+    for mut visibility in scoreboard_query.iter_mut() {
+        if *visibility == Visibility::Hidden {
+            *visibility = Visibility::Inherited;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+fn set_scoreboard_visibility(
+    scoreboard_query: &mut Query<&mut Visibility, With<HudScoreboard>>,
+    new_visibility: Visibility,
+) {
+    for mut visibility in scoreboard_query.iter_mut() {
+        *visibility = new_visibility;
+    }
+}
+
+// the scoreboard doubles as the results screen
+pub fn handle_hud_results_enter(mut scoreboard_query: Query<&mut Visibility, With<HudScoreboard>>) {
+    set_scoreboard_visibility(&mut scoreboard_query, Visibility::Inherited);
+}
+
+pub fn handle_hud_results_exit(mut scoreboard_query: Query<&mut Visibility, With<HudScoreboard>>) {
+    set_scoreboard_visibility(&mut scoreboard_query, Visibility::Hidden);
+}
+
+pub fn handle_hud_scoreboard(
+    scoreboard: Res<Scoreboard>,
+    game: Res<Game>,
+    scoreboard_query: Query<Entity, With<HudScoreboard>>,
+    row_query: Query<Entity, With<HudScoreboardRow>>,
+    mut commands: Commands,
+) {
+    if !scoreboard.is_changed() {
+        return;
+    }
+
+    let result = scoreboard_query.get_single();
+    if result.is_err() {
+        return;
+    }
+
+    let scoreboard_entity = result.unwrap();
+
+    for entity in row_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let row_style = Style {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(HUD_MARGIN),
+        ..default()
+    };
+
+    commands.entity(scoreboard_entity).with_children(|parent| {
+        parent
+            .spawn((
+                NodeBundle {
+                    style: row_style.clone(),
+                    ..default()
+                },
+                HudScoreboardRow {},
+            ))
+            .with_children(|row| {
+                spawn_text(row, "".to_string(), HUD_SWATCH_SIZE);
+                spawn_text(row, "player".to_string(), 160.0);
+                spawn_text(row, "score".to_string(), 60.0);
+                spawn_text(row, "ping".to_string(), 60.0);
+            });
+
+        // team totals come first in team rounds
+        for team in scoreboard.teams.iter() {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: row_style.clone(),
+                        ..default()
+                    },
+                    HudScoreboardRow {},
+                ))
+                .with_children(|row| {
+                    row.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(HUD_SWATCH_SIZE),
+                            height: Val::Px(HUD_SWATCH_SIZE),
+                            ..default()
+                        },
+                        background_color: team.color.into(),
+                        ..default()
+                    });
+                    spawn_text(row, format!("team {:}", team.name), 160.0);
+                    spawn_text(row, format!("{:}", team.score), 60.0);
+                    spawn_text(row, "".to_string(), 60.0);
+                });
+        }
+
+        for entry in scoreboard.entries.iter() {
+            let mut name = entry.name.clone();
+            if game.local_player_uuid.is_some()
+                && game.local_player_uuid.unwrap() == entry.player_uuid
+            {
+                name = format!("{:} (you)", name);
+            }
+
+            let mut ping = "-".to_string();
+            if entry.ping.is_some() {
+                ping = format!("{:.0}ms", entry.ping.unwrap() * 1000.0);
+            }
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: row_style.clone(),
+                        ..default()
+                    },
+                    HudScoreboardRow {},
+                ))
+                .with_children(|row| {
+                    row.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(HUD_SWATCH_SIZE),
+                            height: Val::Px(HUD_SWATCH_SIZE),
+                            ..default()
+                        },
+                        background_color: entry.color.into(),
+                        ..default()
+                    });
+                    spawn_text(row, name, 160.0);
+                    spawn_text(row, format!("{:}", entry.score), 60.0);
+                    spawn_text(row, ping, 60.0);
+                });
+        }
+    });
+}
+
+pub fn handle_hud_health_bar(
+    scoreboard: Res<Scoreboard>,
+    game: Res<Game>,
+    mut health_bar_query: Query<&mut Style, With<HudHealthBar>>,
+) {
+    if !scoreboard.is_changed() || game.local_player_uuid.is_none() {
+        return;
+    }
+
+    let local_player_uuid = game.local_player_uuid.unwrap();
+
+    for entry in scoreboard.entries.iter() {
+        if entry.player_uuid != local_player_uuid {
+            continue;
+        }
+
+        for mut style in health_bar_query.iter_mut() {
+            style.width = Val::Percent((entry.health / entry.max_health).clamp(0.0, 1.0) * 100.0);
+        }
+    }
+}
+
+pub fn handle_hud_weapon_status(
+    weapon_status: Res<WeaponStatus>,
+    mut weapon_status_query: Query<&mut Text, With<HudWeaponStatus>>,
+    mut weapon_bar_query: Query<(&mut Style, &mut BackgroundColor), With<HudWeaponBar>>,
+) {
+    if !weapon_status.is_changed() || weapon_status.weapon.is_none() {
+        return;
+    }
+
+    let weapon = weapon_status.weapon.clone().unwrap();
+
+    let mut value = weapon.weapon_type.clone();
+    let mut fill = 0.0;
+    let mut color = HUD_HEAT_BAR_COLOR;
+
+    if weapon.reload_progress.is_some() {
+        value = format!("{:} reloading", value);
+        fill = weapon.reload_progress.unwrap();
+    } else if weapon.ammo.is_some() && weapon.magazine_size.is_some() {
+        value = format!(
+            "{:} {:}/{:}",
+            value,
+            weapon.ammo.unwrap(),
+            weapon.magazine_size.unwrap()
+        );
+        fill = weapon.ammo.unwrap() as f32 / weapon.magazine_size.unwrap().max(1) as f32;
+    }
+
+    if weapon.heat.is_some() {
+        fill = weapon.heat.unwrap();
+    }
+
+    if weapon.is_overheated {
+        value = format!("{:} overheated", value);
+        color = HUD_OVERHEATED_COLOR;
+    }
+
+    for mut text in weapon_status_query.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+
+    for (mut style, mut background_color) in weapon_bar_query.iter_mut() {
+        style.width = Val::Percent(fill.clamp(0.0, 1.0) * 100.0);
+        *background_color = color.into();
+    }
+}
+
+pub fn handle_hud_connection_status(
+    game: Res<Game>,
+    mut connection_status_query: Query<&mut Text, With<HudConnectionStatus>>,
+) {
+    if !game.is_changed() {
+        return;
+    }
+
+    for mut text in connection_status_query.iter_mut() {
+        if game.is_connected {
+            text.sections[0].value = "connected".to_string();
+            text.sections[0].style.color = HUD_CONNECTED_COLOR;
+        } else {
+            text.sections[0].value = "disconnected".to_string();
+            text.sections[0].style.color = HUD_DISCONNECTED_COLOR;
+        }
+    }
+}
+
+pub fn handle_kill_event(
+    mut kill_event_reader: EventReader<KillEvent>,
+    mut kill_feed: ResMut<KillFeed>,
+    time: Res<Time>,
+) {
+    for kill_event in kill_event_reader.read() {
+        kill_feed
+            .entries
+            .push((time.elapsed_seconds_f64(), kill_event.clone()));
+
+        if kill_feed.entries.len() > HUD_KILL_FEED_LENGTH_MAX {
+            kill_feed.entries.remove(0);
+        }
+    }
+}
+
+pub fn handle_hud_kill_feed(
+    time: Res<Time>,
+    mut kill_feed: ResMut<KillFeed>,
+    scoreboard: Res<Scoreboard>,
+    kill_feed_query: Query<Entity, With<HudKillFeed>>,
+    kill_feed_entry_query: Query<Entity, With<HudKillFeedEntry>>,
+    mut commands: Commands,
+) {
+    // only take a mutable borrow if something has expired, so change detection stays useful
+    let is_expired = |(received_at, _): &(f64, KillEvent)| {
+        time.elapsed_seconds_f64() - received_at > HUD_KILL_FEED_EXPIRY_SECONDS
+    };
+
+    if kill_feed.entries.iter().any(is_expired) {
+        kill_feed.entries.retain(|entry| !is_expired(entry));
+    }
+
+    if !kill_feed.is_changed() {
+        return;
+    }
+
+    let result = kill_feed_query.get_single();
+    if result.is_err() {
+        return;
+    }
+
+    let kill_feed_entity = result.unwrap();
+
+    for entity in kill_feed_entry_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    commands.entity(kill_feed_entity).with_children(|parent| {
+        for (_, kill_event) in kill_feed.entries.iter() {
+            let victim = get_display_name(&scoreboard, kill_event.victim_uuid);
+
+            let mut value = format!("{:} died", victim);
+            if kill_event.killer_uuid.is_some() {
+                value = format!(
+                    "{:} killed {:}",
+                    get_display_name(&scoreboard, kill_event.killer_uuid.unwrap()),
+                    victim
+                );
+            }
+
+            parent.spawn((
+                TextBundle::from_section(value, get_text_style(HUD_TEXT_COLOR)),
+                HudKillFeedEntry {},
+            ));
+        }
+    });
+}
+
+pub fn handle_hud_round_status(
+    time: Res<Time>,
+    game: Res<Game>,
+    round: Res<Round>,
+    scoreboard: Res<Scoreboard>,
+    mut round_status_query: Query<&mut Text, With<HudRoundStatus>>,
+) {
+    let synced_time =
+        time.elapsed_seconds_f64() + (game.server_time_at_join - game.client_time_at_join);
+
+    let mut seconds_left = 0.0;
+    if round.ends_at.is_some() {
+        seconds_left = (round.ends_at.unwrap() - synced_time).max(0.0);
+    }
+
+    let value = match round.match_state {
+        MatchState::WaitingForPlayers => format!(
+            "waiting for players ({:}/{:})",
+            scoreboard.entries.len(),
+            round.min_players
+        ),
+        MatchState::Countdown => format!(
+            "round {:} starts in {:.0}",
+            round.round_number + 1,
+            seconds_left.ceil()
+        ),
+        MatchState::InRound => {
+            let mut value = format!("round {:}", round.round_number);
+
+            if round.ends_at.is_some() {
+                let seconds_left = seconds_left.ceil() as u64;
+                value = format!(
+                    "{:} - {:}:{:02} left",
+                    value,
+                    seconds_left / 60,
+                    seconds_left % 60
+                );
+            }
+
+            if round.score_limit.is_some() {
+                value = format!("{:} - first to {:}", value, round.score_limit.unwrap());
+            }
+
+            value
+        }
+        MatchState::Results => {
+            if round.winner_team.is_some() {
+                format!(
+                    "team {:} wins round {:}!",
+                    get_team_name(round.winner_team.unwrap()),
+                    round.round_number
+                )
+            } else if round.winner_uuid.is_some() {
+                format!(
+                    "{:} wins round {:}!",
+                    get_display_name(&scoreboard, round.winner_uuid.unwrap()),
+                    round.round_number
+                )
+            } else {
+                format!("round {:} is a draw", round.round_number)
+            }
+        }
+    };
+
+    for mut text in round_status_query.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+pub fn handle_hud_objective_status(
+    game_mode_status: Res<GameModeStatus>,
+    mut objective_status_query: Query<&mut Text, With<HudObjectiveStatus>>,
+) {
+    if !game_mode_status.is_changed() {
+        return;
+    }
+
+    let mut value = "".to_string();
+
+    if game_mode_status.game_mode.is_some() {
+        let game_mode = game_mode_status.game_mode.clone().unwrap();
+
+        let objectives: Vec<String> = game_mode
+            .objectives
+            .iter()
+            .enumerate()
+            .map(|(team, objective)| format!("{:} {:.0}", get_team_name(team as u32), objective))
+            .collect();
+
+        value = format!(
+            "{:}: {:} (first to {:.0})",
+            game_mode.objective_name.unwrap_or_default(),
+            objectives.join(" - "),
+            game_mode.objective_limit
+        );
+    }
+
+    for mut text in objective_status_query.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
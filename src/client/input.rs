@@ -1,127 +1,130 @@
-use bevy::input::ButtonInput;
+use bevy::input::gamepad::{GamepadAxis, GamepadAxisType, GamepadButton, Gamepads};
+use bevy::input::{Axis, ButtonInput};
 use bevy::log::trace;
 use bevy::prelude::{
-    default, BackgroundColor, Button, ButtonBundle, Changed, Commands, Component, EventReader,
-    EventWriter, Interaction, KeyCode, PositionType, Query, Res, ResMut, Resource, Style, Val,
-    With,
+    DetectChanges, DetectChangesMut, EventReader, EventWriter, KeyCode, Query, Res, ResMut,
+    Resource, With,
 };
 
 use crate::base::helpers::serialize;
+use crate::client::controls::{Controls, ControlsScreen};
+use crate::client::lobby::Lobby;
 use crate::constants::{
-    PLAYER_BACKWARD_KEY, PLAYER_FIRE_KEY, PLAYER_FORWARD_KEY, PLAYER_LEFT_KEY, PLAYER_RIGHT_KEY,
-    UI_BUTTON_HEIGHT, UI_BUTTON_HOVERED, UI_BUTTON_NORMAL, UI_BUTTON_PRESSED, UI_BUTTON_WIDTH,
+    GAMEPAD_STICK_DEADZONE, GAMEPAD_TRIGGER_DEADZONE, INPUT_BUTTON_FIRE, INPUT_BUTTON_SWITCH_WEAPON,
 };
 use crate::identity::entity::Local;
 use crate::identity::player::Player;
-use crate::types::event::InputEvent;
+use crate::types::event::{get_quantized_axis, get_unquantized_axis, InputEvent};
 use crate::types::network::{Container, OutgoingMessageEvent};
 
-#[derive(Debug, Clone, Component)]
-pub struct ButtonRole {
-    pub is_bottom_left: bool,
-    pub is_bottom_right: bool,
+// what any connected gamepads are doing, mixed in with the keyboard; quantized like InputEvent
+#[derive(Debug, Clone, Default, PartialEq, Resource)]
+pub struct GamepadState {
+    pub turn: i8,
+    pub thrust: i8,
+    pub buttons: u8,
 }
 
-#[derive(Debug, Clone, Resource)]
-pub struct ButtonState {
-    pub is_bottom_left_pressed: bool,
-    pub is_bottom_right_pressed: bool,
+// what the on-screen touch controls are doing, mixed in the same way
+#[derive(Debug, Clone, Default, PartialEq, Resource)]
+pub struct TouchState {
+    pub turn: i8,
+    pub thrust: i8,
+    pub buttons: u8,
 }
 
-pub fn spawn_button(
-    commands: &mut Commands,
-    left: f32,
-    top: f32,
-    is_bottom_left: bool,
-    is_bottom_right: bool,
-) {
-    commands.spawn((
-        ButtonBundle {
-            style: Style {
-                width: Val::Px(UI_BUTTON_WIDTH),
-                height: Val::Px(UI_BUTTON_HEIGHT),
-                position_type: PositionType::Absolute,
-                left: Val::Px(left),
-                right: Default::default(),
-                top: Val::Px(top),
-                bottom: Default::default(),
-                ..default()
-            },
-            background_color: UI_BUTTON_NORMAL.into(),
-            ..default()
-        },
-        ButtonRole {
-            is_bottom_left,
-            is_bottom_right,
-        },
-    ));
+// 0.0 inside the deadzone, otherwise scaled so it still goes all the way from 0.0 to 1.0
+pub fn get_past_deadzone(value: f32, deadzone: f32) -> f32 {
+    if value.abs() < deadzone {
+        return 0.0;
+    }
+
+    value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
 }
 
-pub fn handle_input_from_keyboard(
-    player_query: Query<&Player, With<Local>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut input_event_writer: EventWriter<InputEvent>,
+// the left stick steers, along with whatever buttons are bound; buttons count as far as they're
+// pushed, so triggers are analog
+pub fn handle_input_from_gamepad(
+    controls: Res<Controls>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_axes: Res<Axis<GamepadButton>>,
+    button_input: Res<ButtonInput<GamepadButton>>,
+    mut gamepad_state: ResMut<GamepadState>,
 ) {
-    let result = player_query.get_single();
-    if result.is_err() {
-        return;
-    }
+    let mut turn = 0.0;
+    let mut thrust = 0.0;
+    let mut buttons = 0;
+
+    for gamepad in gamepads.iter() {
+        turn += get_past_deadzone(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+                .unwrap_or(0.0),
+            GAMEPAD_STICK_DEADZONE,
+        );
 
-    let player = result.unwrap();
+        // the furthest pushed of the buttons bound to the action
+        let get_value = |action: &str| {
+            let mut value: f32 = 0.0;
 
-    assert!(player.is_local_player);
+            for button_type in controls.get_gamepad_buttons(action) {
+                let button = GamepadButton::new(gamepad, button_type);
 
-    let inputs = vec![
-        PLAYER_LEFT_KEY,
-        PLAYER_RIGHT_KEY,
-        PLAYER_FORWARD_KEY,
-        PLAYER_BACKWARD_KEY,
-        PLAYER_FIRE_KEY,
-    ];
+                // fall back to pressed or not for anything that doesn't report how far
+                let mut button_value = 0.0;
 
-    let any_just_pressed = keyboard_input.any_just_pressed(inputs.clone());
-    let any_just_released = keyboard_input.any_just_released(inputs.clone());
+                if button_axes.get(button).is_some() {
+                    button_value = get_past_deadzone(
+                        button_axes.get(button).unwrap(),
+                        GAMEPAD_TRIGGER_DEADZONE,
+                    );
+                } else if button_input.pressed(button) {
+                    button_value = 1.0;
+                }
 
-    if !(any_just_pressed || any_just_released) {
-        return;
-    }
+                value = value.max(button_value);
+            }
 
-    let is_left = keyboard_input.pressed(PLAYER_LEFT_KEY);
-    let is_right = keyboard_input.pressed(PLAYER_RIGHT_KEY);
-    let is_forward = keyboard_input.pressed(PLAYER_FORWARD_KEY);
-    let is_backward = keyboard_input.pressed(PLAYER_BACKWARD_KEY);
-    let is_firing = keyboard_input.pressed(PLAYER_FIRE_KEY);
-
-    let input = InputEvent {
-        player_uuid: player.player_uuid,
-        is_left,
-        is_right,
-        is_forward,
-        is_backward,
-        is_firing,
-    };
+            value
+        };
 
-    trace!(
-        "handle_input_from_keyboard(); left={:?}, right={:?}, forward={:?}, backward={:?}, fire={:?}",
-        input.is_left,
-        input.is_right,
-        input.is_forward,
-        input.is_backward,
-        input.is_firing
-    );
+        turn += get_value("right") - get_value("left");
+        thrust += get_value("forward") - get_value("backward");
 
-    input_event_writer.send(input);
+        if get_value("fire") > 0.0 {
+            buttons |= INPUT_BUTTON_FIRE;
+        }
+
+        if get_value("switch_weapon") > 0.0 {
+            buttons |= INPUT_BUTTON_SWITCH_WEAPON;
+        }
+    }
+
+    // only a change if something actually changed, so the keyboard handler can tell
+    gamepad_state.set_if_neq(GamepadState {
+        turn: get_quantized_axis(turn),
+        thrust: get_quantized_axis(thrust),
+        buttons,
+    });
 }
 
-pub fn handle_input_from_button(
+// the keyboard, gamepad and touch controls are mixed (any of them can be doing anything), so this
+// runs whenever any of them changes (or the controls screen opens or closes)
+pub fn handle_input_from_keyboard(
     player_query: Query<&Player, With<Local>>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &ButtonRole),
-        (Changed<Interaction>, With<Button>),
-    >,
-    mut button_state: ResMut<ButtonState>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    controls: Res<Controls>,
+    controls_screen: Res<ControlsScreen>,
+    lobby: Res<Lobby>,
+    gamepad_state: Res<GamepadState>,
+    touch_state: Res<TouchState>,
     mut input_event_writer: EventWriter<InputEvent>,
 ) {
+    // the keys are still being used to type a name
+    if !lobby.is_submitted {
+        return;
+    }
+
     let result = player_query.get_single();
     if result.is_err() {
         return;
@@ -131,68 +134,64 @@ pub fn handle_input_from_button(
 
     assert!(player.is_local_player);
 
-    let mut was_input = false;
-
-    for (interaction, mut color, button_role) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                *color = UI_BUTTON_PRESSED.into();
-
-                if button_role.is_bottom_left {
-                    button_state.is_bottom_left_pressed = true;
-                    was_input = true;
-                }
+    let actions = [
+        "left",
+        "right",
+        "forward",
+        "backward",
+        "fire",
+        "switch_weapon",
+    ];
 
-                if button_role.is_bottom_right {
-                    button_state.is_bottom_right_pressed = true;
-                    was_input = true;
-                }
-            }
-            Interaction::Hovered => {
-                *color = UI_BUTTON_HOVERED.into();
-            }
-            Interaction::None => {
-                *color = UI_BUTTON_NORMAL.into();
+    let inputs = actions
+        .iter()
+        .flat_map(|action| controls.get_keys(action))
+        .collect::<Vec<KeyCode>>();
 
-                if button_role.is_bottom_left {
-                    button_state.is_bottom_left_pressed = false;
-                    was_input = true;
-                }
+    let any_just_pressed = keyboard_input.any_just_pressed(inputs.clone());
+    let any_just_released = keyboard_input.any_just_released(inputs.clone());
 
-                if button_role.is_bottom_right {
-                    button_state.is_bottom_right_pressed = false;
-                    was_input = true;
-                }
-            }
-        }
+    if !(any_just_pressed
+        || any_just_released
+        || gamepad_state.is_changed()
+        || touch_state.is_changed()
+        || controls_screen.is_changed())
+    {
+        return;
     }
 
-    if !was_input {
-        return;
+    let mut keyboard = InputEvent::from_digital(
+        player.player_uuid,
+        controls.is_key_pressed("left", &keyboard_input),
+        controls.is_key_pressed("right", &keyboard_input),
+        controls.is_key_pressed("forward", &keyboard_input),
+        controls.is_key_pressed("backward", &keyboard_input),
+        controls.is_key_pressed("fire", &keyboard_input),
+        controls.is_key_pressed("switch_weapon", &keyboard_input),
+    );
+
+    // keys pressed while (re)binding them shouldn't also fly the ship, so the keyboard lets go of
+    // everything while the controls screen is open
+    if controls_screen.is_open {
+        keyboard = InputEvent::new(player.player_uuid, 0.0, 0.0, 0);
     }
 
-    let is_left = button_state.is_bottom_left_pressed && !button_state.is_bottom_right_pressed;
-    let is_right = !button_state.is_bottom_left_pressed && button_state.is_bottom_right_pressed;
-    let is_forward = button_state.is_bottom_left_pressed && button_state.is_bottom_right_pressed;
-    let is_backward = false;
-    let is_firing = false;
-
-    let input = InputEvent {
-        player_uuid: player.player_uuid,
-        is_left,
-        is_right,
-        is_forward,
-        is_backward,
-        is_firing,
-    };
+    let input = InputEvent::new(
+        player.player_uuid,
+        keyboard.get_turn()
+            + get_unquantized_axis(gamepad_state.turn)
+            + get_unquantized_axis(touch_state.turn),
+        keyboard.get_thrust()
+            + get_unquantized_axis(gamepad_state.thrust)
+            + get_unquantized_axis(touch_state.thrust),
+        keyboard.buttons | gamepad_state.buttons | touch_state.buttons,
+    );
 
     trace!(
-        "handle_input_from_button(); left={:?}, right={:?}, forward={:?}, backward={:?}, fire={:?}",
-        input.is_left,
-        input.is_right,
-        input.is_forward,
-        input.is_backward,
-        input.is_firing
+        "handle_input_from_keyboard(); turn={:?}, thrust={:?}, buttons={:?}",
+        input.turn,
+        input.thrust,
+        input.buttons
     );
 
     input_event_writer.send(input);
@@ -208,25 +207,18 @@ pub fn handle_input_event(
             not_session_uuid: None,
             message: serialize(Container {
                 message_type: "input".to_string(),
-                join: None,
-                spawn: None,
                 input: Some(input.clone()),
-                update: None,
-                despawn: None,
-                leave: None,
-                collision: None,
+                ..Default::default()
             }),
         };
 
         outgoing_message_event_writer.send(outgoing_message);
 
         trace!(
-            "handle_input_event(); left={:?}, right={:?}, forward={:?}, backward={:?}, fire={:?}",
-            input.is_left,
-            input.is_right,
-            input.is_forward,
-            input.is_backward,
-            input.is_firing
+            "handle_input_event(); turn={:?}, thrust={:?}, buttons={:?}",
+            input.turn,
+            input.thrust,
+            input.buttons
         );
     }
 }
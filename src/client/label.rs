@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use bevy::prelude::{
+    default, Commands, Component, Entity, JustifyText, Query, Text, Text2dBundle, TextStyle,
+    Transform, Vec3, Without,
+};
+use uuid::Uuid;
+
+use crate::constants::{HUD_TEXT_COLOR, PLAYER_NAME_FONT_SIZE, PLAYER_NAME_OFFSET};
+use crate::identity::player::Player;
+
+#[derive(Debug, Clone, Component)]
+pub struct PlayerLabel {
+    pub player_uuid: Uuid,
+}
+
+// labels are kept as separate entities (rather than children) so they don't inherit the ship's
+// rotation and scale
+pub fn handle_player_label(
+    player_query: Query<(&Player, &Transform)>,
+    mut label_query: Query<(Entity, &PlayerLabel, &mut Transform, &mut Text), Without<Player>>,
+    mut commands: Commands,
+) {
+    let mut labelled_player_uuids = HashSet::new();
+
+    for (entity, label, mut label_transform, mut text) in label_query.iter_mut() {
+        let player = player_query
+            .iter()
+            .find(|(player, _)| player.player_uuid == label.player_uuid);
+
+        if player.is_none() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let (player, transform) = player.unwrap();
+
+        label_transform.translation =
+            transform.translation + Vec3::new(0.0, PLAYER_NAME_OFFSET, 1.0);
+
+        if text.sections[0].value != player.name {
+            text.sections[0].value = player.name.clone();
+        }
+
+        labelled_player_uuids.insert(label.player_uuid);
+    }
+
+    for (player, transform) in player_query.iter() {
+        if labelled_player_uuids.contains(&player.player_uuid) {
+            continue;
+        }
+
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    player.name.clone(),
+                    TextStyle {
+                        font_size: PLAYER_NAME_FONT_SIZE,
+                        color: HUD_TEXT_COLOR,
+                        ..default()
+                    },
+                )
+                .with_justify(JustifyText::Center),
+                transform: Transform::from_translation(
+                    transform.translation + Vec3::new(0.0, PLAYER_NAME_OFFSET, 1.0),
+                ),
+                ..default()
+            },
+            PlayerLabel {
+                player_uuid: player.player_uuid,
+            },
+        ));
+    }
+}
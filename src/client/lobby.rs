@@ -0,0 +1,263 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::{
+    default, AlignItems, BorderColor, BuildChildren, Button, ButtonBundle, Changed, Commands,
+    Component, DespawnRecursiveExt, DetectChanges, Entity, EventReader, EventWriter, FlexDirection,
+    Interaction, JustifyContent, NodeBundle, Query, Res, ResMut, Resource, Style, Text, TextBundle,
+    TextStyle, UiRect, Val, With,
+};
+use rand::{thread_rng, Rng};
+use uuid::Uuid;
+
+use crate::base::helpers::serialize;
+use crate::client::controls::ControlsScreen;
+use crate::constants::{
+    HUD_BACKGROUND_COLOR, HUD_FONT_SIZE, HUD_MARGIN, HUD_TEXT_COLOR, LOBBY_SELECTED_COLOR,
+    LOBBY_SWATCH_BORDER, LOBBY_SWATCH_SIZE, PLAYER_COLORS, PLAYER_NAME_LENGTH_MAX,
+    UI_BUTTON_HEIGHT, UI_BUTTON_NORMAL, UI_BUTTON_WIDTH,
+};
+use crate::identity::game::Game;
+use crate::types::event::HelloEvent;
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+#[derive(Debug, Clone, Resource)]
+pub struct Lobby {
+    pub name: String,
+    pub color_index: usize,
+    pub is_submitted: bool,
+    pub is_hello_sent: bool,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct LobbyScreen {}
+
+#[derive(Debug, Clone, Component)]
+pub struct LobbyNameText {}
+
+#[derive(Debug, Clone, Component)]
+pub struct LobbyColorButton {
+    pub color_index: usize,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct LobbyJoinButton {}
+
+pub fn get_lobby() -> Lobby {
+    Lobby {
+        name: "".to_string(),
+        color_index: thread_rng().gen_range(0..PLAYER_COLORS.len()),
+        is_submitted: false,
+        is_hello_sent: false,
+    }
+}
+
+pub fn handle_lobby_setup(mut commands: Commands) {
+    let text_style = TextStyle {
+        font_size: HUD_FONT_SIZE,
+        color: HUD_TEXT_COLOR,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(HUD_MARGIN),
+                    ..default()
+                },
+                background_color: HUD_BACKGROUND_COLOR.into(),
+                ..default()
+            },
+            LobbyScreen {},
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "type a name and pick a colour",
+                text_style.clone(),
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(UI_BUTTON_WIDTH * 2.0),
+                        padding: UiRect::all(Val::Px(HUD_MARGIN / 2.0)),
+                        border: UiRect::all(Val::Px(LOBBY_SWATCH_BORDER)),
+                        ..default()
+                    },
+                    border_color: HUD_TEXT_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|name_box| {
+                    name_box.spawn((
+                        TextBundle::from_section("_", text_style.clone()),
+                        LobbyNameText {},
+                    ));
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(HUD_MARGIN / 2.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|swatches| {
+                    for (color_index, color) in PLAYER_COLORS.iter().enumerate() {
+                        swatches.spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(LOBBY_SWATCH_SIZE),
+                                    height: Val::Px(LOBBY_SWATCH_SIZE),
+                                    border: UiRect::all(Val::Px(LOBBY_SWATCH_BORDER)),
+                                    ..default()
+                                },
+                                background_color: (*color).into(),
+                                ..default()
+                            },
+                            LobbyColorButton { color_index },
+                        ));
+                    }
+                });
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(UI_BUTTON_WIDTH),
+                            height: Val::Px(UI_BUTTON_HEIGHT),
+                            align_items: AlignItems::Center,
+                            justify_content: JustifyContent::Center,
+                            ..default()
+                        },
+                        background_color: UI_BUTTON_NORMAL.into(),
+                        ..default()
+                    },
+                    LobbyJoinButton {},
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section("join", text_style.clone()));
+                });
+        });
+}
+
+pub fn handle_lobby_keyboard(
+    mut keyboard_input_reader: EventReader<KeyboardInput>,
+    controls_screen: Res<ControlsScreen>,
+    mut lobby: ResMut<Lobby>,
+) {
+    for keyboard_input in keyboard_input_reader.read() {
+        if lobby.is_submitted || controls_screen.is_open || !keyboard_input.state.is_pressed() {
+            continue;
+        }
+
+        match &keyboard_input.logical_key {
+            Key::Character(characters) => {
+                for character in characters.chars() {
+                    if lobby.name.chars().count() < PLAYER_NAME_LENGTH_MAX {
+                        lobby.name.push(character);
+                    }
+                }
+            }
+            Key::Space => {
+                if lobby.name.chars().count() < PLAYER_NAME_LENGTH_MAX {
+                    lobby.name.push(' ');
+                }
+            }
+            Key::Backspace => {
+                lobby.name.pop();
+            }
+            Key::Enter => {
+                lobby.is_submitted = true;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn handle_lobby_button(
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&LobbyColorButton>,
+            Option<&LobbyJoinButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut lobby: ResMut<Lobby>,
+) {
+    for (interaction, color_button, join_button) in interaction_query.iter() {
+        if *interaction != Interaction::Pressed || lobby.is_submitted {
+            continue;
+        }
+
+        if color_button.is_some() {
+            lobby.color_index = color_button.unwrap().color_index;
+        }
+
+        if join_button.is_some() {
+            lobby.is_submitted = true;
+        }
+    }
+}
+
+pub fn handle_lobby_ui(
+    lobby: Res<Lobby>,
+    mut name_text_query: Query<&mut Text, With<LobbyNameText>>,
+    mut color_button_query: Query<(&LobbyColorButton, &mut BorderColor)>,
+) {
+    if !lobby.is_changed() {
+        return;
+    }
+
+    for mut text in name_text_query.iter_mut() {
+        text.sections[0].value = format!("{:}_", lobby.name);
+    }
+
+    for (color_button, mut border_color) in color_button_query.iter_mut() {
+        if color_button.color_index == lobby.color_index {
+            *border_color = LOBBY_SELECTED_COLOR.into();
+        } else {
+            *border_color = PLAYER_COLORS[color_button.color_index].into();
+        }
+    }
+}
+
+pub fn handle_lobby_hello(
+    mut lobby: ResMut<Lobby>,
+    game: Res<Game>,
+    lobby_screen_query: Query<Entity, With<LobbyScreen>>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+    mut commands: Commands,
+) {
+    // wait for the player to finish up and for the connection to be ready
+    if !lobby.is_submitted || lobby.is_hello_sent || !game.is_connected {
+        return;
+    }
+
+    // the server fills in the session and has the final say on the name and colour
+    outgoing_message_event_writer.send(OutgoingMessageEvent {
+        session_uuid: None,
+        not_session_uuid: None,
+        message: serialize(Container {
+            message_type: "hello".to_string(),
+            hello: Some(HelloEvent {
+                session_uuid: Uuid::default(),
+                name: lobby.name.trim().to_string(),
+                color: PLAYER_COLORS[lobby.color_index],
+            }),
+            ..Default::default()
+        }),
+    });
+
+    lobby.is_hello_sent = true;
+
+    for entity in lobby_screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use bevy::prelude::{
+    default, BuildChildren, Commands, Component, DespawnRecursiveExt, Entity, NodeBundle,
+    OrthographicProjection, Overflow, PositionType, Query, Res, Style, Transform, UiRect, Val,
+    Vec2, With, Without,
+};
+use uuid::Uuid;
+
+use crate::base::setup::MainCamera;
+use crate::client::touch::TouchControls;
+use crate::constants::{
+    BOUNDS, HALF, HUD_BACKGROUND_COLOR, HUD_MARGIN, MINIMAP_DOT_SIZE, MINIMAP_LOCAL_DOT_SIZE,
+    MINIMAP_VIEW_COLOR, MINIMAP_WIDTH, WINDOW_SIZE,
+};
+use crate::identity::game::Game;
+use crate::identity::player::Player;
+
+#[derive(Debug, Clone, Component)]
+pub struct HudMinimap {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudMinimapView {}
+
+#[derive(Debug, Clone, Component)]
+pub struct HudMinimapDot {
+    pub player_uuid: Uuid,
+}
+
+// as a percentage of the minimap, from the top left
+fn get_minimap_position(translation: Vec2, arena_size: Vec2) -> Vec2 {
+    Vec2::new(
+        (translation.x / arena_size.x + HALF) * 100.0,
+        (HALF - translation.y / arena_size.y) * 100.0,
+    )
+}
+
+pub fn handle_minimap_setup(mut commands: Commands) {
+    // the whole arena in the bottom right, whatever its size; the height is fixed up once joined
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(HUD_MARGIN),
+                    bottom: Val::Px(HUD_MARGIN),
+                    width: Val::Px(MINIMAP_WIDTH),
+                    height: Val::Px(MINIMAP_WIDTH * BOUNDS.y / BOUNDS.x),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: HUD_BACKGROUND_COLOR.into(),
+                ..default()
+            },
+            HudMinimap {},
+        ))
+        .with_children(|parent| {
+            // what the camera can see
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        ..default()
+                    },
+                    background_color: MINIMAP_VIEW_COLOR.into(),
+                    ..default()
+                },
+                HudMinimapView {},
+            ));
+        });
+}
+
+pub fn handle_minimap(
+    game: Res<Game>,
+    touch_controls: Res<TouchControls>,
+    player_query: Query<(&Player, &Transform)>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    mut minimap_query: Query<(Entity, &mut Style), With<HudMinimap>>,
+    mut view_query: Query<&mut Style, (With<HudMinimapView>, Without<HudMinimap>)>,
+    mut dot_query: Query<
+        (Entity, &HudMinimapDot, &mut Style),
+        (Without<HudMinimap>, Without<HudMinimapView>),
+    >,
+    mut commands: Commands,
+) {
+    let arena_size = game.arena_size;
+
+    // the touch controls take up both bottom corners, so it moves to the middle
+    let (left, right) = if touch_controls.is_shown {
+        (Val::Px((WINDOW_SIZE.x - MINIMAP_WIDTH) * HALF), Val::Auto)
+    } else {
+        (Val::Auto, Val::Px(HUD_MARGIN))
+    };
+
+    for (_, mut style) in minimap_query.iter_mut() {
+        let height = Val::Px(MINIMAP_WIDTH * arena_size.y / arena_size.x);
+
+        if style.height != height {
+            style.height = height;
+        }
+
+        if style.left != left || style.right != right {
+            style.left = left;
+            style.right = right;
+        }
+    }
+
+    for (camera_transform, projection) in camera_query.iter() {
+        let view_size = projection.area.size();
+        let top_left = get_minimap_position(
+            camera_transform.translation.truncate() + Vec2::new(-view_size.x, view_size.y) * HALF,
+            arena_size,
+        );
+
+        for mut style in view_query.iter_mut() {
+            style.left = Val::Percent(top_left.x);
+            style.top = Val::Percent(top_left.y);
+            style.width = Val::Percent(view_size.x / arena_size.x * 100.0);
+            style.height = Val::Percent(view_size.y / arena_size.y * 100.0);
+        }
+    }
+
+    let mut dotted_player_uuids = HashSet::new();
+
+    for (entity, dot, mut style) in dot_query.iter_mut() {
+        let player = player_query
+            .iter()
+            .find(|(player, _)| player.player_uuid == dot.player_uuid);
+
+        if player.is_none() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let (_, transform) = player.unwrap();
+        let position = get_minimap_position(transform.translation.truncate(), arena_size);
+
+        style.left = Val::Percent(position.x);
+        style.top = Val::Percent(position.y);
+
+        dotted_player_uuids.insert(dot.player_uuid);
+    }
+
+    let minimap = minimap_query.iter().next();
+    if minimap.is_none() {
+        return;
+    }
+
+    let (minimap_entity, _) = minimap.unwrap();
+
+    for (player, transform) in player_query.iter() {
+        if dotted_player_uuids.contains(&player.player_uuid) {
+            continue;
+        }
+
+        let size = if player.is_local_player {
+            MINIMAP_LOCAL_DOT_SIZE
+        } else {
+            MINIMAP_DOT_SIZE
+        };
+
+        let position = get_minimap_position(transform.translation.truncate(), arena_size);
+
+        commands.entity(minimap_entity).with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(position.x),
+                        top: Val::Percent(position.y),
+                        width: Val::Px(size),
+                        height: Val::Px(size),
+                        // so the middle of the dot is on the ship
+                        margin: UiRect {
+                            left: Val::Px(-size * HALF),
+                            top: Val::Px(-size * HALF),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    background_color: player.color.into(),
+                    ..default()
+                },
+                HudMinimapDot {
+                    player_uuid: player.player_uuid,
+                },
+            ));
+        });
+    }
+}
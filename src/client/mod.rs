@@ -1,8 +1,25 @@
 pub mod app;
+pub mod camera;
+pub mod controls;
+pub mod effect;
 pub mod error;
+pub mod game_mode;
+pub mod ghost;
+pub mod hud;
 pub mod input;
+pub mod label;
+pub mod lobby;
+pub mod minimap;
 pub mod moveable;
 pub mod network;
+pub mod ping;
+pub mod round;
+pub mod scoreboard;
 pub mod setup;
+pub mod ship;
+pub mod touch;
 pub mod update;
+pub mod vfx;
+pub mod viewport;
+pub mod weapon;
 pub mod websocket;
@@ -2,10 +2,11 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use bevy::log::trace;
-use bevy::prelude::{EventReader, EventWriter, NonSend};
+use bevy::prelude::{EventReader, EventWriter, NonSend, ResMut};
 use uuid::Uuid;
 
 use crate::client::websocket::WebSocketClient;
+use crate::identity::game::Game;
 use crate::types::network::{CloseEvent, IncomingMessageEvent, OpenEvent, OutgoingMessageEvent};
 
 pub fn handle_websocket_client(
@@ -63,3 +64,15 @@ pub fn handle_websocket_client(
         });
     }
 }
+
+pub fn handle_open_event(mut open_event_reader: EventReader<OpenEvent>, mut game: ResMut<Game>) {
+    for _ in open_event_reader.read() {
+        game.is_connected = true;
+    }
+}
+
+pub fn handle_close_event(mut close_event_reader: EventReader<CloseEvent>, mut game: ResMut<Game>) {
+    for _ in close_event_reader.read() {
+        game.is_connected = false;
+    }
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::{EventReader, EventWriter, Res, ResMut, Time};
+
+use crate::base::helpers::serialize;
+use crate::constants::PING_RATE_SECONDS;
+use crate::identity::game::Game;
+use crate::types::event::{PingEvent, PongEvent};
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+pub fn handle_ping(
+    time: Res<Time>,
+    mut game: ResMut<Game>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    if game.local_player_uuid.is_none() {
+        return;
+    }
+
+    if time.elapsed_seconds_f64() - game.last_ping_at < PING_RATE_SECONDS {
+        return;
+    }
+
+    // the last round trip time rides along so the server can share it on the scoreboard
+    outgoing_message_event_writer.send(OutgoingMessageEvent {
+        session_uuid: None,
+        not_session_uuid: None,
+        message: serialize(Container {
+            message_type: "ping".to_string(),
+            ping: Some(PingEvent {
+                player_uuid: game.local_player_uuid.unwrap(),
+                client_time: time.elapsed_seconds_f64(),
+                last_round_trip_time: game.round_trip_time,
+            }),
+            ..Default::default()
+        }),
+    });
+
+    game.last_ping_at = time.elapsed_seconds_f64();
+}
+
+pub fn handle_pong_event(
+    mut pong_event_reader: EventReader<PongEvent>,
+    mut game: ResMut<Game>,
+    time: Res<Time>,
+) {
+    for pong in pong_event_reader.read() {
+        game.round_trip_time = Some(time.elapsed_seconds_f64() - pong.client_time);
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::{EventReader, NextState, ResMut};
+
+use crate::identity::round::{MatchState, Round};
+use crate::types::event::RoundEvent;
+
+pub fn handle_round_event(
+    mut round_event_reader: EventReader<RoundEvent>,
+    mut round: ResMut<Round>,
+    mut next_match_state: ResMut<NextState<MatchState>>,
+) {
+    for round_event in round_event_reader.read() {
+        *round = round_event.round.clone();
+
+        // the server is authoritative; the client state just follows along
+        next_match_state.set(round.match_state);
+    }
+}
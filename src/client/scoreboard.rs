@@ -0,0 +1,15 @@
+use bevy::prelude::{EventReader, ResMut};
+
+use crate::identity::scoreboard::Scoreboard;
+use crate::types::event::ScoreboardEvent;
+
+pub fn handle_scoreboard_event(
+    mut scoreboard_event_reader: EventReader<ScoreboardEvent>,
+    mut scoreboard: ResMut<Scoreboard>,
+) {
+    for scoreboard_event in scoreboard_event_reader.read() {
+        scoreboard.entries = scoreboard_event.entries.clone();
+        scoreboard.teams = scoreboard_event.teams.clone();
+        scoreboard.last_update_at = scoreboard_event.server_time;
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+
+use bevy::asset::{Assets, Handle};
+use bevy::color::Mix;
+use bevy::math::{Vec2, Vec3};
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, DetectChanges, Entity, Mesh, Query, Res, ResMut,
+    Time, Transform, Visibility, Without,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::dynamics::Velocity;
+use rand::{thread_rng, Rng};
+use uuid::Uuid;
+
+use crate::client::vfx::{spawn_particle, Vfx};
+use crate::constants::{
+    HALF, PLAYER_DAMAGE_TINT_COLOR, PLAYER_DAMAGE_TINT_MAX, PLAYER_DAMAGE_TINT_STEPS,
+    PLAYER_FLAME_COLOR, PLAYER_FLAME_FLICKER, PLAYER_FLAME_LENGTH_MULTIPLIER, PLAYER_PUFF_COLOR,
+    PLAYER_PUFF_LINEAR_VELOCITY, PLAYER_PUFF_RATE_SECONDS, ZERO,
+};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::player::Player;
+use crate::identity::scoreboard::Scoreboard;
+
+#[derive(Debug, Clone, Component)]
+pub struct ShipFlame {
+    pub player_uuid: Uuid,
+}
+
+fn get_forward(transform: &Transform) -> Vec2 {
+    transform
+        .rotation
+        .mul_vec3(Vec3::new(ZERO, 1.0, ZERO))
+        .truncate()
+}
+
+// a flickering flame out the back of any ship that's thrusting (going by the last input the
+// server saw); flames are separate entities (like labels) so they can be stretched independently
+pub fn handle_ship_flame(
+    player_query: Query<(&Player, &Transform)>,
+    mut flame_query: Query<(Entity, &ShipFlame, &mut Transform, &mut Visibility), Without<Player>>,
+    mut asset_cache: ResMut<AssetCache>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let mut flamed_player_uuids = HashSet::new();
+
+    for (entity, flame, mut flame_transform, mut visibility) in flame_query.iter_mut() {
+        let player = player_query
+            .iter()
+            .find(|(player, _)| player.player_uuid == flame.player_uuid);
+
+        if player.is_none() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let (player, transform) = player.unwrap();
+
+        flamed_player_uuids.insert(flame.player_uuid);
+
+        let is_thrusting =
+            player.last_input.is_some() && player.last_input.as_ref().unwrap().get_thrust() > ZERO;
+
+        if !is_thrusting {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let flicker = 1.0 + (thread_rng().gen::<f32>() * 2.0 - 1.0) * PLAYER_FLAME_FLICKER;
+        let length = transform.scale.y * PLAYER_FLAME_LENGTH_MULTIPLIER * flicker;
+
+        // the ship's (and the flame's) triangle sits a quarter of its height from its flat edge
+        let offset = transform.scale.y * HALF * HALF + length * HALF * HALF;
+
+        flame_transform.translation = (transform.translation.truncate()
+            - get_forward(transform) * offset)
+            .extend(transform.translation.z - 0.01);
+        flame_transform.rotation = transform.rotation;
+        flame_transform.scale = Vec3::new(transform.scale.x * HALF, length, 1.0);
+
+        *visibility = Visibility::Inherited;
+    }
+
+    for (player, _) in player_query.iter() {
+        if flamed_player_uuids.contains(&player.player_uuid) {
+            continue;
+        }
+
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: asset_cache.get_mesh(Shape::Flame, &mut meshes),
+                material: asset_cache.get_material(PLAYER_FLAME_COLOR, &mut materials),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            ShipFlame {
+                player_uuid: player.player_uuid,
+            },
+        ));
+    }
+}
+
+// puffs out of the nose on the side away from the turn
+pub fn handle_ship_puff(
+    player_query: Query<(&Player, &Transform, &Velocity)>,
+    mut vfx: ResMut<Vfx>,
+    time: Res<Time>,
+) {
+    if time.elapsed_seconds_f64() - vfx.last_puff_at < PLAYER_PUFF_RATE_SECONDS {
+        return;
+    }
+
+    vfx.last_puff_at = time.elapsed_seconds_f64();
+
+    for (player, transform, velocity) in player_query.iter() {
+        if player.last_input.is_none() {
+            continue;
+        }
+
+        let input = player.last_input.as_ref().unwrap();
+        if input.get_turn() == ZERO {
+            continue;
+        }
+
+        let mut side = 1.0;
+        if input.get_turn() > ZERO {
+            side = -1.0;
+        }
+
+        let nose = transform.rotation.mul_vec3(Vec3::new(
+            side * transform.scale.x * HALF * HALF,
+            transform.scale.y * HALF * HALF,
+            ZERO,
+        ));
+
+        let linvel = transform
+            .rotation
+            .mul_vec3(Vec3::new(side * PLAYER_PUFF_LINEAR_VELOCITY, ZERO, ZERO))
+            .truncate();
+
+        spawn_particle(
+            &mut vfx,
+            (transform.translation + nose).truncate(),
+            linvel + velocity.linvel,
+            PLAYER_PUFF_COLOR,
+        );
+    }
+}
+
+// ships darken as they take damage, in a few steps so the tinted materials can be shared (the cache
+// lets go of a step once no ship is on it any more)
+pub fn handle_ship_tint(
+    scoreboard: Res<Scoreboard>,
+    mut player_query: Query<(&Player, &mut Handle<ColorMaterial>)>,
+    mut asset_cache: ResMut<AssetCache>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !scoreboard.is_changed() {
+        return;
+    }
+
+    for (player, mut material) in player_query.iter_mut() {
+        let entry = scoreboard
+            .entries
+            .iter()
+            .find(|entry| entry.player_uuid == player.player_uuid);
+
+        if entry.is_none() {
+            continue;
+        }
+
+        let entry = entry.unwrap();
+
+        let damage = 1.0 - (entry.health / entry.max_health).clamp(0.0, 1.0);
+        let step = (damage * PLAYER_DAMAGE_TINT_STEPS).round() / PLAYER_DAMAGE_TINT_STEPS;
+
+        let color = player
+            .color
+            .mix(&PLAYER_DAMAGE_TINT_COLOR, step * PLAYER_DAMAGE_TINT_MAX);
+
+        *material = asset_cache.get_material(color, &mut materials);
+    }
+}
@@ -0,0 +1,356 @@
+use bevy::input::touch::{Touch, Touches};
+use bevy::math::Vec2;
+use bevy::prelude::{
+    default, BackgroundColor, BorderRadius, Camera, Commands, Component, DetectChangesMut, Mut,
+    NodeBundle, PositionType, Query, Res, ResMut, Resource, Style, Val, Visibility, With, Without,
+};
+use bevy::ui::UiScale;
+use web_sys::window;
+
+use crate::base::setup::MainCamera;
+use crate::client::controls::ControlsScreen;
+use crate::client::input::{get_past_deadzone, TouchState};
+use crate::constants::{
+    HALF, INPUT_BUTTON_FIRE, INPUT_BUTTON_SWITCH_WEAPON, TOUCH_COLOR, TOUCH_FIRE_CENTER,
+    TOUCH_FIRE_RADIUS, TOUCH_JOYSTICK_DEADZONE, TOUCH_JOYSTICK_KNOB_RADIUS, TOUCH_JOYSTICK_RADIUS,
+    TOUCH_JOYSTICK_REST, TOUCH_PRESSED_COLOR, TOUCH_SWITCH_WEAPON_CENTER,
+    TOUCH_SWITCH_WEAPON_RADIUS, WINDOW_SIZE,
+};
+use crate::identity::game::Game;
+use crate::types::event::get_quantized_axis;
+
+// each control is claimed by its own touch, so steering and firing at once works; positions are in
+// UI units (i.e. WINDOW_SIZE, however the view is scaled)
+#[derive(Debug, Clone, Default, Resource)]
+pub struct TouchControls {
+    pub is_shown: bool,
+    pub joystick_touch_id: Option<u64>,
+    // where the joystick touch started and where it is now
+    pub joystick_origin: Vec2,
+    pub joystick_position: Vec2,
+    pub fire_touch_id: Option<u64>,
+    pub switch_weapon_touch_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct TouchControlsUi {}
+
+#[derive(Debug, Clone, Component)]
+pub struct TouchJoystickBase {}
+
+#[derive(Debug, Clone, Component)]
+pub struct TouchJoystickKnob {}
+
+#[derive(Debug, Clone, Component)]
+pub struct TouchFireButton {}
+
+#[derive(Debug, Clone, Component)]
+pub struct TouchSwitchWeaponButton {}
+
+fn is_touch_device() -> bool {
+    let window = window();
+    if window.is_none() {
+        return false;
+    }
+
+    window.unwrap().navigator().max_touch_points() > 0
+}
+
+fn get_circle_style(center: Vec2, radius: f32) -> Style {
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(center.x - radius),
+        top: Val::Px(center.y - radius),
+        width: Val::Px(radius * 2.0),
+        height: Val::Px(radius * 2.0),
+        ..default()
+    }
+}
+
+fn spawn_circle(commands: &mut Commands, center: Vec2, radius: f32, marker: impl Component) {
+    commands.spawn((
+        NodeBundle {
+            style: get_circle_style(center, radius),
+            background_color: TOUCH_COLOR.into(),
+            border_radius: BorderRadius::MAX,
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        TouchControlsUi {},
+        marker,
+    ));
+}
+
+// touches are in logical pixels from the top left of the window, but the UI starts at the top left
+// of the letterboxed view and is scaled
+fn get_ui_position(touch: &Touch, camera: &Camera, ui_scale: f32) -> Vec2 {
+    let mut offset = Vec2::ZERO;
+
+    let viewport = camera.logical_viewport_rect();
+    if viewport.is_some() {
+        offset = viewport.unwrap().min;
+    }
+
+    (touch.position() - offset) / ui_scale
+}
+
+// -1.0 to 1.0 each way, with y up
+fn get_joystick_value(touch_controls: &TouchControls) -> Vec2 {
+    if touch_controls.joystick_touch_id.is_none() {
+        return Vec2::ZERO;
+    }
+
+    let offset = touch_controls.joystick_position - touch_controls.joystick_origin;
+
+    (Vec2::new(offset.x, -offset.y) / TOUCH_JOYSTICK_RADIUS).clamp_length_max(1.0)
+}
+
+fn set_circle(
+    mut style: Mut<Style>,
+    mut background_color: Mut<BackgroundColor>,
+    center: Vec2,
+    radius: f32,
+    is_pressed: bool,
+) {
+    let left = Val::Px(center.x - radius);
+    let top = Val::Px(center.y - radius);
+
+    if style.left != left || style.top != top {
+        style.left = left;
+        style.top = top;
+    }
+
+    let color = if is_pressed {
+        TOUCH_PRESSED_COLOR
+    } else {
+        TOUCH_COLOR
+    };
+
+    if background_color.0 != color {
+        background_color.0 = color;
+    }
+}
+
+// shown up front on touch screens, or as soon as a touch turns up on anything else
+pub fn handle_touch_setup(mut touch_controls: ResMut<TouchControls>, mut commands: Commands) {
+    touch_controls.is_shown = is_touch_device();
+
+    spawn_circle(
+        &mut commands,
+        TOUCH_JOYSTICK_REST,
+        TOUCH_JOYSTICK_RADIUS,
+        TouchJoystickBase {},
+    );
+
+    spawn_circle(
+        &mut commands,
+        TOUCH_JOYSTICK_REST,
+        TOUCH_JOYSTICK_KNOB_RADIUS,
+        TouchJoystickKnob {},
+    );
+
+    spawn_circle(
+        &mut commands,
+        TOUCH_FIRE_CENTER,
+        TOUCH_FIRE_RADIUS,
+        TouchFireButton {},
+    );
+
+    spawn_circle(
+        &mut commands,
+        TOUCH_SWITCH_WEAPON_CENTER,
+        TOUCH_SWITCH_WEAPON_RADIUS,
+        TouchSwitchWeaponButton {},
+    );
+}
+
+pub fn handle_touch(
+    touches: Res<Touches>,
+    ui_scale: Res<UiScale>,
+    game: Res<Game>,
+    controls_screen: Res<ControlsScreen>,
+    camera_query: Query<&Camera, With<MainCamera>>,
+    mut touch_controls: ResMut<TouchControls>,
+    mut touch_state: ResMut<TouchState>,
+) {
+    if touches.any_just_pressed() && !touch_controls.is_shown {
+        touch_controls.is_shown = true;
+    }
+
+    // let go of (or cancelled) touches free up their controls
+    if touch_controls.joystick_touch_id.is_some()
+        && touches
+            .get_pressed(touch_controls.joystick_touch_id.unwrap())
+            .is_none()
+    {
+        touch_controls.joystick_touch_id = None;
+    }
+
+    if touch_controls.fire_touch_id.is_some()
+        && touches
+            .get_pressed(touch_controls.fire_touch_id.unwrap())
+            .is_none()
+    {
+        touch_controls.fire_touch_id = None;
+    }
+
+    if touch_controls.switch_weapon_touch_id.is_some()
+        && touches
+            .get_pressed(touch_controls.switch_weapon_touch_id.unwrap())
+            .is_none()
+    {
+        touch_controls.switch_weapon_touch_id = None;
+    }
+
+    let camera = camera_query.get_single();
+
+    // nothing new gets claimed unless the controls are actually up
+    if camera.is_ok() && game.local_player_uuid.is_some() && !controls_screen.is_open {
+        let camera = camera.unwrap();
+
+        for touch in touches.iter_just_pressed() {
+            // a quick tap can start and end in the same frame, leaving nothing to hold on to
+            if touches.get_pressed(touch.id()).is_none() {
+                continue;
+            }
+
+            let position = get_ui_position(touch, camera, ui_scale.0);
+
+            if position.distance(TOUCH_FIRE_CENTER) <= TOUCH_FIRE_RADIUS {
+                touch_controls.fire_touch_id = Some(touch.id());
+                continue;
+            }
+
+            if position.distance(TOUCH_SWITCH_WEAPON_CENTER) <= TOUCH_SWITCH_WEAPON_RADIUS {
+                touch_controls.switch_weapon_touch_id = Some(touch.id());
+                continue;
+            }
+
+            if position.x < WINDOW_SIZE.x * HALF && touch_controls.joystick_touch_id.is_none() {
+                touch_controls.joystick_touch_id = Some(touch.id());
+                touch_controls.joystick_origin = position;
+                touch_controls.joystick_position = position;
+            }
+        }
+
+        if touch_controls.joystick_touch_id.is_some() {
+            if let Some(touch) = touches.get_pressed(touch_controls.joystick_touch_id.unwrap()) {
+                touch_controls.joystick_position = get_ui_position(touch, camera, ui_scale.0);
+            } else {
+                touch_controls.joystick_touch_id = None;
+            }
+        }
+    }
+
+    let value = get_joystick_value(&touch_controls);
+
+    let mut buttons = 0;
+
+    if touch_controls.fire_touch_id.is_some() {
+        buttons |= INPUT_BUTTON_FIRE;
+    }
+
+    if touch_controls.switch_weapon_touch_id.is_some() {
+        buttons |= INPUT_BUTTON_SWITCH_WEAPON;
+    }
+
+    touch_state.set_if_neq(TouchState {
+        turn: get_quantized_axis(get_past_deadzone(value.x, TOUCH_JOYSTICK_DEADZONE)),
+        thrust: get_quantized_axis(get_past_deadzone(value.y, TOUCH_JOYSTICK_DEADZONE)),
+        buttons,
+    });
+}
+
+// the joystick follows where the touch started, with the knob pulled towards the thumb
+pub fn handle_touch_ui(
+    touch_controls: Res<TouchControls>,
+    game: Res<Game>,
+    controls_screen: Res<ControlsScreen>,
+    mut visibility_query: Query<&mut Visibility, With<TouchControlsUi>>,
+    mut base_query: Query<(&mut Style, &mut BackgroundColor), With<TouchJoystickBase>>,
+    mut knob_query: Query<
+        (&mut Style, &mut BackgroundColor),
+        (With<TouchJoystickKnob>, Without<TouchJoystickBase>),
+    >,
+    mut fire_query: Query<
+        (&mut Style, &mut BackgroundColor),
+        (
+            With<TouchFireButton>,
+            Without<TouchJoystickBase>,
+            Without<TouchJoystickKnob>,
+        ),
+    >,
+    mut switch_weapon_query: Query<
+        (&mut Style, &mut BackgroundColor),
+        (
+            With<TouchSwitchWeaponButton>,
+            Without<TouchJoystickBase>,
+            Without<TouchJoystickKnob>,
+            Without<TouchFireButton>,
+        ),
+    >,
+) {
+    let visibility = if touch_controls.is_shown
+        && game.local_player_uuid.is_some()
+        && !controls_screen.is_open
+    {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    for mut circle_visibility in visibility_query.iter_mut() {
+        circle_visibility.set_if_neq(visibility);
+    }
+
+    let is_steering = touch_controls.joystick_touch_id.is_some();
+
+    let origin = if is_steering {
+        touch_controls.joystick_origin
+    } else {
+        TOUCH_JOYSTICK_REST
+    };
+
+    let value = get_joystick_value(&touch_controls);
+    let knob = origin + Vec2::new(value.x, -value.y) * TOUCH_JOYSTICK_RADIUS;
+
+    for (style, background_color) in base_query.iter_mut() {
+        set_circle(
+            style,
+            background_color,
+            origin,
+            TOUCH_JOYSTICK_RADIUS,
+            false,
+        );
+    }
+
+    for (style, background_color) in knob_query.iter_mut() {
+        set_circle(
+            style,
+            background_color,
+            knob,
+            TOUCH_JOYSTICK_KNOB_RADIUS,
+            is_steering,
+        );
+    }
+
+    for (style, background_color) in fire_query.iter_mut() {
+        set_circle(
+            style,
+            background_color,
+            TOUCH_FIRE_CENTER,
+            TOUCH_FIRE_RADIUS,
+            touch_controls.fire_touch_id.is_some(),
+        );
+    }
+
+    for (style, background_color) in switch_weapon_query.iter_mut() {
+        set_circle(
+            style,
+            background_color,
+            TOUCH_SWITCH_WEAPON_CENTER,
+            TOUCH_SWITCH_WEAPON_RADIUS,
+            touch_controls.switch_weapon_touch_id.is_some(),
+        );
+    }
+}
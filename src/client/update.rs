@@ -1,19 +1,25 @@
 use bevy::prelude::{EventReader, Query};
 
 use crate::behaviour::moveable::Moveable;
+use crate::identity::player::Player;
 use crate::types::event::UpdateEvent;
 
 pub fn handle_update_event(
     mut update_event_reader: EventReader<UpdateEvent>,
-    mut moveable_query: Query<&mut Moveable>,
+    mut moveable_query: Query<(&mut Moveable, Option<&mut Player>)>,
 ) {
     for update in update_event_reader.read() {
-        for mut moveable in moveable_query.iter_mut() {
+        for (mut moveable, player) in moveable_query.iter_mut() {
             if update.entity_uuid != moveable.entity_uuid {
                 continue;
             }
 
             moveable.unhandled_updates.insert(0, update.clone());
+
+            // only used for drawing; the server is the one that acts on inputs
+            if player.is_some() {
+                player.unwrap().last_input = update.input.clone();
+            }
         }
     }
 }
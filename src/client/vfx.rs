@@ -0,0 +1,308 @@
+use bevy::asset::{Assets, Handle};
+use bevy::math::{EulerRot, Quat, Vec2, Vec3};
+use bevy::prelude::{
+    default, Color, ColorMaterial, Commands, Component, Entity, EventReader, Mesh, Query, Res,
+    ResMut, Resource, Time, Transform, Visibility, With,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::dynamics::Velocity;
+use rand::prelude::SliceRandom;
+use rand::{thread_rng, Rng};
+
+use crate::behaviour::collideable::{CollisionEvent, CollisionKind, CollisionType};
+use crate::constants::{
+    DEGREES_MAX, HALF, MATERIAL_SCALE, PARTICLE_CHANGE_RATE_SECONDS, PARTICLE_COUNT_MAX,
+    PARTICLE_DIMENSION_MULTIPLIER, PARTICLE_EXPIRY_SECONDS, PARTICLE_EXPLOSION_COUNT,
+    PARTICLE_FIRE_COLORS, PARTICLE_LINEAR_VELOCITY, PARTICLE_LINEAR_VELOCITY_CHANGE,
+    PARTICLE_MUZZLE_FLASH_COUNT, PARTICLE_MUZZLE_FLASH_SPREAD_DEGREES, PARTICLE_SPARK_COUNT,
+    PARTICLE_TRAIL_RATE_SECONDS, PARTICLE_TRAIL_SPEED_MIN, ZERO,
+};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::player::Player;
+use crate::types::event::{KillEvent, SpawnEvent};
+
+// purely cosmetic and local to the client; the server never hears about these
+#[derive(Debug, Clone, Component)]
+pub struct Particle {
+    pub linvel: Vec2,
+    pub changes_at: f64,
+    pub expires_at: f64,
+    pub is_active: bool,
+}
+
+// particles are a fixed pool of entities (drawn with the shared particle mesh) that get recycled
+// oldest first, so there's never more than PARTICLE_COUNT_MAX and nothing to leak
+#[derive(Debug, Clone, Resource)]
+pub struct Vfx {
+    pub last_trail_at: f64,
+    pub last_puff_at: f64,
+    pub particles: Vec<Entity>,
+    pub next_particle: usize,
+    // (translation, linvel, color) waiting for a particle from the pool
+    pub pending: Vec<(Vec2, Vec2, Color)>,
+}
+
+pub fn spawn_particle(vfx: &mut Vfx, translation: Vec2, linvel: Vec2, color: Color) {
+    vfx.pending.push((translation, linvel, color));
+}
+
+pub fn handle_vfx_setup(
+    mut asset_cache: ResMut<AssetCache>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut commands: Commands,
+) {
+    let mesh = asset_cache.get_mesh(Shape::Particle, &mut meshes);
+
+    let mut vfx = Vfx {
+        last_trail_at: 0.0,
+        last_puff_at: 0.0,
+        particles: vec![],
+        next_particle: 0,
+        pending: vec![],
+    };
+
+    let material = asset_cache.get_material(Color::srgb(1.0, 1.0, 1.0), &mut materials);
+
+    let mut size = Vec3::splat(MATERIAL_SCALE);
+    size.y *= PARTICLE_DIMENSION_MULTIPLIER;
+    size.x *= PARTICLE_DIMENSION_MULTIPLIER;
+
+    for _ in 0..PARTICLE_COUNT_MAX {
+        let material_mesh = MaterialMesh2dBundle {
+            mesh: mesh.clone(),
+            material: material.clone(),
+            transform: Transform::from_scale(size),
+            visibility: Visibility::Hidden,
+            ..default()
+        };
+
+        let particle = Particle {
+            linvel: Vec2::ZERO,
+            changes_at: 0.0,
+            expires_at: 0.0,
+            is_active: false,
+        };
+
+        vfx.particles
+            .push(commands.spawn((material_mesh, particle)).id());
+    }
+
+    commands.insert_resource(vfx);
+}
+
+// count particles fanned out evenly from translation, between the two angles (in degrees)
+fn spawn_burst(
+    vfx: &mut Vfx,
+    translation: Vec2,
+    count: u32,
+    angles: (f32, f32),
+    speed: f32,
+    colors: &[Color],
+) {
+    let (from, to) = angles;
+
+    for i in 0..count {
+        let angle = from + (to - from) * (i as f32 / count as f32);
+
+        let linvel = Quat::from_rotation_z(f32::to_radians(angle))
+            .mul_vec3(Vec3::new(ZERO, speed, ZERO))
+            .truncate();
+
+        let color = *colors.choose(&mut thread_rng()).unwrap();
+
+        spawn_particle(vfx, translation, linvel, color);
+    }
+}
+
+// hit sparks wherever two solid things bump into each other; fire if a player was involved
+pub fn handle_collision_event_for_vfx(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut vfx: ResMut<Vfx>,
+) {
+    for collision in collision_event_reader.read() {
+        if collision.kind != CollisionKind::Contact || collision.collider_a.transform.is_none() {
+            continue;
+        }
+
+        let translation = collision
+            .collider_a
+            .transform
+            .clone()
+            .unwrap()
+            .translation
+            .truncate();
+
+        let mut colors = vec![Color::srgb(1.0, 1.0, 1.0)];
+
+        if collision.collider_a.collision_type == Some(CollisionType::Player)
+            || collision.collider_b.collision_type == Some(CollisionType::Player)
+        {
+            colors = PARTICLE_FIRE_COLORS.to_vec();
+        }
+
+        spawn_burst(
+            &mut vfx,
+            translation,
+            PARTICLE_SPARK_COUNT,
+            (ZERO, DEGREES_MAX * HALF),
+            PARTICLE_LINEAR_VELOCITY,
+            &colors,
+        );
+    }
+}
+
+// a ball of fire where a ship was destroyed
+pub fn handle_kill_event_for_vfx(
+    mut kill_event_reader: EventReader<KillEvent>,
+    player_query: Query<(&Player, &Transform)>,
+    mut vfx: ResMut<Vfx>,
+) {
+    for kill_event in kill_event_reader.read() {
+        let player = player_query
+            .iter()
+            .find(|(player, _)| player.player_uuid == kill_event.victim_uuid);
+
+        if player.is_none() {
+            continue;
+        }
+
+        let (_, transform) = player.unwrap();
+
+        spawn_burst(
+            &mut vfx,
+            transform.translation.truncate(),
+            PARTICLE_EXPLOSION_COUNT,
+            (ZERO, DEGREES_MAX),
+            PARTICLE_LINEAR_VELOCITY,
+            &PARTICLE_FIRE_COLORS,
+        );
+    }
+}
+
+// a little flash in front of the gun for each shot, in the weapon's colour
+pub fn handle_spawn_event_for_vfx(
+    mut spawn_event_reader: EventReader<SpawnEvent>,
+    mut vfx: ResMut<Vfx>,
+) {
+    for spawn in spawn_event_reader.read() {
+        if spawn.entity_type != "projectile" || spawn.transform.is_none() {
+            continue;
+        }
+
+        let transform = spawn.transform.clone().unwrap().to_transform();
+        let (angle, _, _) = transform.rotation.to_euler(EulerRot::ZYX);
+        let angle = f32::to_degrees(angle);
+
+        spawn_burst(
+            &mut vfx,
+            transform.translation.truncate(),
+            PARTICLE_MUZZLE_FLASH_COUNT,
+            (
+                angle - PARTICLE_MUZZLE_FLASH_SPREAD_DEGREES * HALF,
+                angle + PARTICLE_MUZZLE_FLASH_SPREAD_DEGREES * HALF,
+            ),
+            PARTICLE_LINEAR_VELOCITY * HALF,
+            &[spawn.color.unwrap_or(Color::srgb(1.0, 1.0, 1.0))],
+        );
+    }
+}
+
+// a trail out the back of any ship that's moving forwards
+pub fn handle_thruster_trail(
+    player_query: Query<(&Transform, &Velocity), With<Player>>,
+    mut vfx: ResMut<Vfx>,
+    time: Res<Time>,
+) {
+    if time.elapsed_seconds_f64() - vfx.last_trail_at < PARTICLE_TRAIL_RATE_SECONDS {
+        return;
+    }
+
+    vfx.last_trail_at = time.elapsed_seconds_f64();
+
+    for (transform, velocity) in player_query.iter() {
+        let forward = transform
+            .rotation
+            .mul_vec3(Vec3::new(ZERO, 1.0, ZERO))
+            .truncate();
+
+        if velocity.linvel.length() < PARTICLE_TRAIL_SPEED_MIN
+            || velocity.linvel.dot(forward) <= ZERO
+        {
+            continue;
+        }
+
+        let translation = transform.translation.truncate() - forward * transform.scale.y * HALF;
+
+        spawn_particle(
+            &mut vfx,
+            translation,
+            -forward * PARTICLE_LINEAR_VELOCITY_CHANGE + velocity.linvel * HALF,
+            *PARTICLE_FIRE_COLORS.choose(&mut thread_rng()).unwrap(),
+        );
+    }
+}
+
+// hands out pooled particles to whatever's pending, then moves them along (jittering about a bit)
+// until they expire
+pub fn handle_particle(
+    mut particle_query: Query<(
+        &mut Particle,
+        &mut Transform,
+        &mut Handle<ColorMaterial>,
+        &mut Visibility,
+    )>,
+    mut vfx: ResMut<Vfx>,
+    mut asset_cache: ResMut<AssetCache>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    let now = time.elapsed_seconds_f64();
+
+    let pending: Vec<(Vec2, Vec2, Color)> = vfx.pending.drain(..).collect();
+
+    for (translation, linvel, color) in pending {
+        let entity = vfx.particles[vfx.next_particle];
+        vfx.next_particle = (vfx.next_particle + 1) % vfx.particles.len();
+
+        let result = particle_query.get_mut(entity);
+        if result.is_err() {
+            continue;
+        }
+
+        let (mut particle, mut transform, mut material, mut visibility) = result.unwrap();
+
+        particle.linvel = linvel;
+        particle.changes_at = now + PARTICLE_CHANGE_RATE_SECONDS;
+        particle.expires_at = now + PARTICLE_EXPIRY_SECONDS + thread_rng().gen::<f64>();
+        particle.is_active = true;
+
+        transform.translation = translation.extend(0.0);
+        *material = asset_cache.get_material(color, &mut materials);
+        *visibility = Visibility::Inherited;
+    }
+
+    for (mut particle, mut transform, _, mut visibility) in particle_query.iter_mut() {
+        if !particle.is_active {
+            continue;
+        }
+
+        if now >= particle.expires_at {
+            particle.is_active = false;
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        if now >= particle.changes_at {
+            let rnd: f32 = thread_rng().gen();
+
+            particle.linvel += Quat::from_rotation_z(f32::to_radians(rnd * DEGREES_MAX))
+                .mul_vec3(Vec3::new(ZERO, PARTICLE_LINEAR_VELOCITY_CHANGE, ZERO))
+                .truncate();
+
+            particle.changes_at = now + PARTICLE_CHANGE_RATE_SECONDS;
+        }
+
+        transform.translation += (particle.linvel * time.delta_seconds()).extend(0.0);
+    }
+}
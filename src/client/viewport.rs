@@ -0,0 +1,76 @@
+use bevy::math::{UVec2, Vec2};
+use bevy::prelude::{default, Camera, Camera2dBundle, Commands, Query, ResMut, Window, With};
+use bevy::render::camera::{ClearColorConfig, Viewport};
+use bevy::render::view::RenderLayers;
+use bevy::ui::UiScale;
+use bevy::window::PrimaryWindow;
+
+use crate::base::setup::MainCamera;
+use crate::constants::{LETTERBOX_COLOR, LETTERBOX_RENDER_LAYER, WINDOW_SIZE};
+
+// a camera that sees nothing (nothing else is on its render layer), behind the main one, just to
+// paint the bars around the letterboxed view
+pub fn handle_viewport_setup(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: -1,
+                clear_color: ClearColorConfig::Custom(LETTERBOX_COLOR),
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(LETTERBOX_RENDER_LAYER),
+    ));
+}
+
+// fits the biggest WINDOW_SIZE-shaped view in the middle of the window (in physical pixels, so
+// it's sharp on high DPI screens) and scales the UI to match; redone whenever the window changes
+pub fn handle_viewport(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, With<MainCamera>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let window = window_query.get_single();
+    if window.is_err() {
+        return;
+    }
+
+    let window = window.unwrap();
+
+    let physical_size = Vec2::new(
+        window.physical_width() as f32,
+        window.physical_height() as f32,
+    );
+
+    // minimised, or not laid out yet
+    if physical_size.min_element() < 1.0 {
+        return;
+    }
+
+    let scale = (physical_size / WINDOW_SIZE).min_element();
+    let size = (WINDOW_SIZE * scale).round();
+    let position = ((physical_size - size) / 2.0).floor();
+
+    let viewport = Viewport {
+        physical_position: UVec2::new(position.x as u32, position.y as u32),
+        physical_size: UVec2::new(size.x as u32, size.y as u32),
+        ..default()
+    };
+
+    for mut camera in camera_query.iter_mut() {
+        let is_changed = camera.viewport.is_none()
+            || camera.viewport.as_ref().unwrap().physical_position != viewport.physical_position
+            || camera.viewport.as_ref().unwrap().physical_size != viewport.physical_size;
+
+        if is_changed {
+            camera.viewport = Some(viewport.clone());
+        }
+    }
+
+    // the UI is laid out in logical pixels, so take the device pixel ratio back out
+    let new_ui_scale = scale / window.scale_factor();
+    if ui_scale.0 != new_ui_scale {
+        ui_scale.0 = new_ui_scale;
+    }
+}
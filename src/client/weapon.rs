@@ -0,0 +1,26 @@
+use bevy::prelude::{EventReader, Res, ResMut, Resource};
+
+use crate::identity::game::Game;
+use crate::types::event::WeaponEvent;
+
+// the local player's weapon state, as last told by the server
+#[derive(Debug, Clone, Resource)]
+pub struct WeaponStatus {
+    pub weapon: Option<WeaponEvent>,
+}
+
+pub fn handle_weapon_event(
+    mut weapon_event_reader: EventReader<WeaponEvent>,
+    game: Res<Game>,
+    mut weapon_status: ResMut<WeaponStatus>,
+) {
+    for weapon_event in weapon_event_reader.read() {
+        if game.local_player_uuid.is_none()
+            || game.local_player_uuid.unwrap() != weapon_event.player_uuid
+        {
+            continue;
+        }
+
+        weapon_status.weapon = Some(weapon_event.clone());
+    }
+}
@@ -4,7 +4,7 @@ use std::rc::Rc;
 use uuid::Uuid;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
-use web_sys::{window, BinaryType, ErrorEvent, Location, MessageEvent, WebSocket};
+use web_sys::{window, BinaryType, CloseEvent, ErrorEvent, Location, MessageEvent, WebSocket};
 
 use crate::base::helpers::deserialize;
 
@@ -51,14 +51,23 @@ impl WebSocketClient {
         ws.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
         onmessage_callback.forget();
 
+        let error_close_events = Rc::clone(&close_events);
         let onerror_callback = Closure::<dyn FnMut(_)>::new(move |_e: ErrorEvent| {
-            let mut close_events = close_events.as_ref().borrow_mut();
+            let mut close_events = error_close_events.as_ref().borrow_mut();
             close_events.push(Uuid::default());
         });
 
         ws.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
         onerror_callback.forget();
 
+        let onclose_callback = Closure::<dyn FnMut(_)>::new(move |_e: CloseEvent| {
+            let mut close_events = close_events.as_ref().borrow_mut();
+            close_events.push(Uuid::default());
+        });
+
+        ws.set_onclose(Some(onclose_callback.as_ref().unchecked_ref()));
+        onclose_callback.forget();
+
         web_socket_client
     }
 
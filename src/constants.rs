@@ -1,5 +1,6 @@
 use std::f32::consts::PI;
 
+use bevy::input::gamepad::GamepadButtonType;
 use bevy::math::Vec2;
 use bevy::prelude::{Color, KeyCode};
 
@@ -11,20 +12,30 @@ pub const RADIANS_TO_DEGREES: f32 = 180.0 / PI;
 
 // app
 pub const TITLE: &str = "eds-game-for-ftp-game-jam-2022";
+// the initial window size and how much of the arena is in view at once; the view is letterboxed and
+// scaled (UI included, so the UI pixel constants are in these units) to fit the actual window
+pub const WINDOW_SIZE: Vec2 = Vec2::new(891.0, 445.0);
+// the arena size for maps that don't have one
 pub const BOUNDS: Vec2 = Vec2::new(891.0, 445.0);
 pub const PIXELS_PER_METER: f32 = 1.0;
 pub const BASE_TIME_STEP: f64 = 1.0 / 30.0;
 pub const BASE_TIME_STEP_NAME: &str = "base_time_step";
 pub const BACKGROUND_COLOR: Color = Color::srgb(0.41, 0.41, 0.41);
+pub const LETTERBOX_COLOR: Color = Color::srgb(0.0, 0.0, 0.0);
+pub const LETTERBOX_RENDER_LAYER: usize = 1;
 
 // server
 pub const LISTEN_HOST: &str = "0.0.0.0";
 pub const LISTEN_PORT: i32 = 8080;
+// the only messages the server takes from clients; everything else only ever comes from the server
+pub const CLIENT_MESSAGE_TYPES: [&str; 3] = ["hello", "input", "ping"];
 
 // common
 pub const MATERIAL_SCALE: f32 = 36.0;
 pub const FRICTION_COEFFICIENT: f32 = 0.7;
 pub const RESTITUTION_COEFFICIENT: f32 = 0.3;
+// how close to an edge (as a fraction of scale) before something shows up on the other side too
+pub const GHOST_MARGIN_MULTIPLIER: f32 = 0.75;
 
 // player
 pub const PLAYER_HEIGHT_MULTIPLIER: f32 = 1.25;
@@ -35,40 +46,272 @@ pub const PLAYER_COLLIDER_BALL_RADIUS: f32 = 0.44;
 pub const PLAYER_DENSITY: f32 = 2.0;
 pub const PLAYER_LINEAR_DAMPING: f32 = 1.0;
 pub const PLAYER_ANGULAR_DAMPING: f32 = 1.0;
-pub const PLAYER_FORWARD_KEY: KeyCode = KeyCode::ArrowUp;
-pub const PLAYER_BACKWARD_KEY: KeyCode = KeyCode::ArrowDown;
-pub const PLAYER_LEFT_KEY: KeyCode = KeyCode::ArrowLeft;
-pub const PLAYER_RIGHT_KEY: KeyCode = KeyCode::ArrowRight;
-pub const PLAYER_FIRE_KEY: KeyCode = KeyCode::AltLeft;
+// the default bindings (they can be changed on the controls screen)
+pub const PLAYER_FORWARD_KEYS: [KeyCode; 2] = [KeyCode::ArrowUp, KeyCode::KeyW];
+pub const PLAYER_BACKWARD_KEYS: [KeyCode; 2] = [KeyCode::ArrowDown, KeyCode::KeyS];
+pub const PLAYER_LEFT_KEYS: [KeyCode; 2] = [KeyCode::ArrowLeft, KeyCode::KeyA];
+pub const PLAYER_RIGHT_KEYS: [KeyCode; 2] = [KeyCode::ArrowRight, KeyCode::KeyD];
+pub const PLAYER_FIRE_KEYS: [KeyCode; 1] = [KeyCode::Space];
+pub const PLAYER_SWITCH_WEAPON_KEYS: [KeyCode; 1] = [KeyCode::KeyQ];
+pub const PLAYER_FORWARD_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::RightTrigger2;
+pub const PLAYER_BACKWARD_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::LeftTrigger2;
+pub const PLAYER_LEFT_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::DPadLeft;
+pub const PLAYER_RIGHT_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::DPadRight;
+pub const PLAYER_FIRE_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::South;
+pub const PLAYER_SWITCH_WEAPON_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::North;
+// how far the stick / triggers have to go before they count
+pub const GAMEPAD_STICK_DEADZONE: f32 = 0.25;
+pub const GAMEPAD_TRIGGER_DEADZONE: f32 = 0.15;
+// steps either side of 0 for the turn and thrust axes when sent over the network
+pub const INPUT_AXIS_STEPS: f32 = 32.0;
+pub const INPUT_BUTTON_FIRE: u8 = 1 << 0;
+pub const INPUT_BUTTON_SWITCH_WEAPON: u8 = 1 << 1;
 pub const PLAYER_ANGULAR_VELOCITY_MAX: f32 = 10.0 / 3.0;
 pub const PLAYER_ANGULAR_VELOCITY_STEP: f32 = 1.0 / 2.0;
 pub const PLAYER_LINEAR_VELOCITY_MAX: f32 = (10.0 / 3.0) * 2.0;
 pub const PLAYER_NETWORK_UPDATE_RATE_SECONDS: f64 = 1.0 / 15.0;
 pub const PLAYER_NETWORK_EMA_SMOOTHING_FACTOR: f64 = 0.99;
 
+pub const PLAYER_NAME_LENGTH_MAX: usize = 16;
+pub const PLAYER_NAME_DEFAULT: &str = "player";
+pub const PLAYER_NAME_OFFSET: f32 = MATERIAL_SCALE;
+pub const PLAYER_NAME_FONT_SIZE: f32 = 14.0;
+pub const PLAYER_COLORS: [Color; 8] = [
+    Color::srgb(0.90, 0.10, 0.10),
+    Color::srgb(0.95, 0.55, 0.10),
+    Color::srgb(0.95, 0.90, 0.15),
+    Color::srgb(0.20, 0.80, 0.20),
+    Color::srgb(0.10, 0.80, 0.80),
+    Color::srgb(0.20, 0.35, 0.95),
+    Color::srgb(0.60, 0.25, 0.90),
+    Color::srgb(0.95, 0.40, 0.75),
+];
+pub const PLAYER_HEALTH_MAX: f32 = 100.0;
+pub const PLAYER_FLAME_COLOR: Color = Color::srgb(1.0, 0.6, 0.1);
+pub const PLAYER_FLAME_LENGTH_MULTIPLIER: f32 = 0.6;
+pub const PLAYER_FLAME_FLICKER: f32 = 0.3;
+pub const PLAYER_PUFF_RATE_SECONDS: f64 = 0.08;
+pub const PLAYER_PUFF_LINEAR_VELOCITY: f32 = 120.0;
+pub const PLAYER_PUFF_COLOR: Color = Color::srgb(0.85, 0.85, 0.9);
+// how far towards the tint colour a ship gets as it's damaged, in steps so materials can be shared
+pub const PLAYER_DAMAGE_TINT_COLOR: Color = Color::srgb(0.25, 0.0, 0.0);
+pub const PLAYER_DAMAGE_TINT_MAX: f32 = 0.6;
+pub const PLAYER_DAMAGE_TINT_STEPS: f32 = 4.0;
+pub const PLAYER_KILL_SCORE: i32 = 500;
+
 // weapon
 pub const WEAPON_FIRE_RATE_SECONDS: f64 = 0.25;
+pub const WEAPON_STARTING_TYPES: [&str; 3] = ["blaster", "spread", "rapid"];
+// fraction of full damage / size a charged weapon fires with when released immediately
+pub const WEAPON_CHARGE_MIN: f32 = 0.25;
+pub const WEAPON_HOMING_RANGE: f32 = 600.0;
+pub const WEAPON_HEAT_COOLDOWN_PER_SECOND: f32 = 0.35;
+// how long firing is locked out for once the heat gauge is full
+pub const WEAPON_OVERHEAT_SECONDS: f64 = 2.0;
+pub const WEAPON_NETWORK_UPDATE_RATE_SECONDS: f64 = 1.0 / 10.0;
 pub const PROJECTILE_LINEAR_VELOCITY: f32 = (1000.0 / 3.0) * 2.0;
 pub const PROEJCTILE_DIMENSION_MULTIPLIER: f32 = 1.0 / 3.5;
 pub const PROJECTILE_DENSITY: f32 = 50.0;
 pub const PROJECTILE_EXPIRY_SECONDS: f64 = 2.5;
+pub const PROJECTILE_DAMAGE: f32 = 25.0;
 pub const PROJECTILE_NETWORK_UPDATE_RATE_SECONDS: f64 = 1.0 / 15.0;
 pub const PROJECTILE_NETWORK_EMA_SMOOTHING_FACTOR: f64 = 0.95;
 
+// round
+pub const ROUND_MIN_PLAYERS: usize = 2;
+pub const ROUND_COUNTDOWN_SECONDS: f64 = 5.0;
+pub const ROUND_DURATION_SECONDS: f64 = 180.0;
+pub const ROUND_SCORE_LIMIT: i32 = 5000;
+pub const ROUND_RESULTS_SECONDS: f64 = 10.0;
+// 0 (or 1) for free-for-all
+pub const ROUND_TEAM_COUNT: u32 = 0;
+pub const ROUND_FRIENDLY_FIRE: bool = false;
+// one of the game modes in identity::game_mode
+pub const ROUND_GAME_MODE: &str = "deathmatch";
+
+// team
+pub const TEAM_NAMES: [&str; 4] = ["red", "blue", "green", "yellow"];
+// override the colour players picked for themselves
+pub const TEAM_COLORS: [Color; 4] = [
+    Color::srgb(0.90, 0.10, 0.10),
+    Color::srgb(0.20, 0.35, 0.95),
+    Color::srgb(0.20, 0.80, 0.20),
+    Color::srgb(0.95, 0.90, 0.15),
+];
+
+// map
+pub const MAP_EDGE_THICKNESS: f32 = MATERIAL_SCALE;
+// how many random spots to try before giving up on finding a clear one
+pub const MAP_CLEAR_ATTEMPTS: usize = 32;
+pub const WALL_COLOR: Color = Color::srgb(0.25, 0.25, 0.3);
+pub const BOUNDARY_RESTITUTION_COEFFICIENT: f32 = 0.9;
+
+// game modes
+pub const GAME_MODE_NETWORK_UPDATE_RATE_SECONDS: f64 = 1.0 / 10.0;
+pub const FLAG_CAPTURE_LIMIT: f32 = 3.0;
+pub const FLAG_CAPTURE_SCORE: i32 = 1000;
+// dropped flags go back to their base if nobody touches them for this long
+pub const FLAG_RETURN_SECONDS: f64 = 20.0;
+pub const FLAG_DIMENSION_MULTIPLIER: f32 = 0.5;
+pub const FLAG_TOUCH_DISTANCE: f32 = MATERIAL_SCALE;
+pub const BASE_RADIUS: f32 = MATERIAL_SCALE * 1.5;
+// how far out from the middle the bases are, as a fraction of the way to the edge
+pub const BASE_DISTANCE_MULTIPLIER: f32 = 0.8;
+pub const BASE_ALPHA: f32 = 0.3;
+pub const ZONE_RADIUS: f32 = MATERIAL_SCALE * 3.0;
+pub const ZONE_HOLD_LIMIT_SECONDS: f32 = 60.0;
+pub const ZONE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+
+// network
+pub const PING_RATE_SECONDS: f64 = 1.0;
+pub const SCOREBOARD_NETWORK_UPDATE_RATE_SECONDS: f64 = 1.0 / 2.0;
+
+// asteroid
+pub const ASTEROID_SIZE_MAX: u32 = 3;
+// indexed by size
+pub const ASTEROID_DIMENSION_MULTIPLIERS: [f32; 4] = [0.0, 1.0, 1.75, 3.0];
+pub const ASTEROID_SCORES: [i32; 4] = [0, 100, 50, 20];
+pub const ASTEROID_DAMAGES: [f32; 4] = [0.0, 10.0, 20.0, 35.0];
+pub const ASTEROID_POLYGON_RADIUS: f32 = 0.5;
+pub const ASTEROID_POLYGON_SIDES: usize = 11;
+pub const ASTEROID_POLYGON_JITTER: f32 = 0.35;
+pub const ASTEROID_COLOR: Color = Color::srgb(0.6, 0.5, 0.4);
+pub const ASTEROID_DENSITY: f32 = 4.0;
+pub const ASTEROID_LINEAR_VELOCITY: f32 = 60.0;
+pub const ASTEROID_ANGULAR_VELOCITY_MAX: f32 = 1.0;
+pub const ASTEROID_SPLIT_COUNT: usize = 2;
+pub const ASTEROID_SPLIT_ANGLE_DEGREES: f32 = 35.0;
+pub const ASTEROID_SPLIT_VELOCITY_MULTIPLIER: f32 = 1.5;
+pub const ASTEROID_WAVE_SIZE: u32 = 3;
+pub const ASTEROID_WAVE_SIZE_INCREMENT: u32 = 1;
+pub const ASTEROID_WAVE_SIZE_MAX: u32 = 8;
+pub const ASTEROID_WAVE_DELAY_SECONDS: f64 = 5.0;
+pub const ASTEROID_NETWORK_UPDATE_RATE_SECONDS: f64 = 1.0 / 5.0;
+pub const ASTEROID_NETWORK_EMA_SMOOTHING_FACTOR: f64 = 0.95;
+
 // particles
 pub const PARTICLE_EXPIRY_SECONDS: f64 = 0.5;
+// the pool size; the oldest get reused once they're all in use
+pub const PARTICLE_COUNT_MAX: usize = 256;
 pub const PARTICLE_CHANGE_RATE_SECONDS: f64 = 0.05;
 pub const PARTICLE_DIMENSION_MULTIPLIER: f32 = 1.0 / 4.0;
 pub const PARTICLE_LINEAR_VELOCITY: f32 = 1000.0 / 3.0;
 pub const PARTICLE_LINEAR_VELOCITY_CHANGE: f32 = 50.0;
+pub const PARTICLE_SPARK_COUNT: u32 = 4;
+pub const PARTICLE_EXPLOSION_COUNT: u32 = 16;
+pub const PARTICLE_MUZZLE_FLASH_COUNT: u32 = 3;
+pub const PARTICLE_MUZZLE_FLASH_SPREAD_DEGREES: f32 = 40.0;
+pub const PARTICLE_TRAIL_RATE_SECONDS: f64 = 0.05;
+pub const PARTICLE_TRAIL_SPEED_MIN: f32 = 50.0;
+pub const PARTICLE_FIRE_COLORS: [Color; 4] = [
+    Color::srgb(1.0, 1.0, 0.0),
+    Color::srgb(1.0, 0.65, 0.0),
+    Color::srgb(1.0, 0.27, 0.0),
+    Color::srgb(1.0, 0.0, 0.0),
+];
+
+// pickups
+pub const PICKUP_TYPES: [&str; 5] = ["shield", "speed", "weapon", "health", "fire_rate"];
+pub const PICKUP_COLORS: [Color; 5] = [
+    Color::srgb(0.3, 0.6, 1.0),
+    Color::srgb(1.0, 1.0, 0.3),
+    Color::srgb(1.0, 0.4, 1.0),
+    Color::srgb(0.3, 1.0, 0.4),
+    Color::srgb(1.0, 0.6, 0.2),
+];
+pub const PICKUP_DIMENSION_MULTIPLIER: f32 = 0.6;
+pub const PICKUP_SPAWN_RATE_SECONDS: f64 = 8.0;
+pub const PICKUP_COUNT_MAX: usize = 4;
+pub const PICKUP_EXPIRY_SECONDS: f64 = 30.0;
+// how far a new pickup has to be from players, asteroids and other pickups
+pub const PICKUP_CLEAR_RADIUS: f32 = 80.0;
+pub const PICKUP_SPAWN_ATTEMPTS: usize = 10;
+pub const PICKUP_HEALTH_AMOUNT: f32 = 50.0;
+
+// effects
+pub const EFFECT_SHIELD_SECONDS: f64 = 10.0;
+pub const EFFECT_SPEED_SECONDS: f64 = 8.0;
+pub const EFFECT_SPEED_MULTIPLIER: f32 = 1.6;
+pub const EFFECT_SHIELD_AMOUNT: f32 = 60.0;
+pub const EFFECT_FIRE_RATE_SECONDS: f64 = 8.0;
+pub const EFFECT_FIRE_RATE_MULTIPLIER: f32 = 2.0;
+pub const EFFECT_STUN_SECONDS: f64 = 1.5;
 
 // ui
 pub const UI_BUTTON_WIDTH: f32 = 100.0;
 pub const UI_BUTTON_HEIGHT: f32 = 50.0;
 pub const UI_BUTTON_BOTTOM_LEFT_LEFT: f32 = 10.0;
-pub const UI_BUTTON_BOTTOM_LEFT_TOP: f32 = BOUNDS.y - (10.0 + UI_BUTTON_HEIGHT);
-pub const UI_BUTTON_BOTTOM_RIGHT_LEFT: f32 = BOUNDS.x - (10.0 + UI_BUTTON_WIDTH);
-pub const UI_BUTTON_BOTTOM_RIGHT_TOP: f32 = BOUNDS.y - (10.0 + UI_BUTTON_HEIGHT);
+pub const UI_BUTTON_BOTTOM_LEFT_TOP: f32 = WINDOW_SIZE.y - (10.0 + UI_BUTTON_HEIGHT);
+pub const UI_BUTTON_BOTTOM_RIGHT_LEFT: f32 = WINDOW_SIZE.x - (10.0 + UI_BUTTON_WIDTH);
+pub const UI_BUTTON_BOTTOM_RIGHT_TOP: f32 = WINDOW_SIZE.y - (10.0 + UI_BUTTON_HEIGHT);
 pub const UI_BUTTON_NORMAL: Color = Color::srgb(0.15, 0.15, 0.15);
 pub const UI_BUTTON_HOVERED: Color = Color::srgb(0.25, 0.25, 0.25);
 pub const UI_BUTTON_PRESSED: Color = Color::srgb(0.35, 0.75, 0.35);
+
+// hud
+pub const HUD_SCOREBOARD_KEY: KeyCode = KeyCode::Tab;
+pub const HUD_SCOREBOARD_GAMEPAD_BUTTON: GamepadButtonType = GamepadButtonType::Select;
+pub const HUD_MARGIN: f32 = 10.0;
+pub const HUD_FONT_SIZE: f32 = 16.0;
+pub const HUD_SWATCH_SIZE: f32 = 12.0;
+pub const HUD_HEALTH_BAR_WIDTH: f32 = 150.0;
+pub const HUD_HEALTH_BAR_HEIGHT: f32 = 10.0;
+pub const HUD_KILL_FEED_EXPIRY_SECONDS: f64 = 5.0;
+pub const HUD_KILL_FEED_LENGTH_MAX: usize = 5;
+pub const HUD_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+pub const HUD_TEXT_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+pub const HUD_HEALTH_BAR_COLOR: Color = Color::srgb(0.35, 0.75, 0.35);
+pub const HUD_HEAT_BAR_COLOR: Color = Color::srgb(0.95, 0.55, 0.10);
+pub const HUD_OVERHEATED_COLOR: Color = Color::srgb(0.75, 0.35, 0.35);
+pub const HUD_CONNECTED_COLOR: Color = Color::srgb(0.35, 0.75, 0.35);
+pub const HUD_DISCONNECTED_COLOR: Color = Color::srgb(0.75, 0.35, 0.35);
+
+// camera
+pub const CAMERA_FOLLOW_KEY: KeyCode = KeyCode::KeyC;
+pub const CAMERA_SMOOTHING: f32 = 6.0;
+pub const CAMERA_DEADZONE: Vec2 = Vec2::new(60.0, 40.0);
+pub const CAMERA_SHAKE_TRAUMA: f32 = 0.4;
+pub const CAMERA_SHAKE_DECAY: f32 = 1.5;
+pub const CAMERA_SHAKE_OFFSET_MAX: f32 = 8.0;
+
+// minimap
+pub const MINIMAP_WIDTH: f32 = 160.0;
+pub const MINIMAP_DOT_SIZE: f32 = 5.0;
+pub const MINIMAP_LOCAL_DOT_SIZE: f32 = 8.0;
+pub const MINIMAP_VIEW_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+
+// controls
+pub const CONTROLS_ACTIONS: [&str; 8] = [
+    "forward",
+    "backward",
+    "left",
+    "right",
+    "fire",
+    "switch_weapon",
+    "scoreboard",
+    "camera",
+];
+// not rebindable, so there's always a way back in
+pub const CONTROLS_SCREEN_KEY: KeyCode = KeyCode::Escape;
+pub const CONTROLS_STORAGE_KEY: &str = "controls";
+pub const CONTROLS_ACTION_WIDTH: f32 = 120.0;
+pub const CONTROLS_BINDINGS_WIDTH: f32 = 300.0;
+pub const CONTROLS_LISTENING_COLOR: Color = Color::srgb(0.95, 0.55, 0.10);
+
+// touch
+// the joystick appears wherever a touch starts on the left half, otherwise it rests here
+pub const TOUCH_JOYSTICK_REST: Vec2 = Vec2::new(110.0, WINDOW_SIZE.y - 110.0);
+pub const TOUCH_JOYSTICK_RADIUS: f32 = 50.0;
+pub const TOUCH_JOYSTICK_KNOB_RADIUS: f32 = 20.0;
+pub const TOUCH_JOYSTICK_DEADZONE: f32 = 0.15;
+pub const TOUCH_FIRE_CENTER: Vec2 = Vec2::new(WINDOW_SIZE.x - 90.0, WINDOW_SIZE.y - 90.0);
+pub const TOUCH_FIRE_RADIUS: f32 = 45.0;
+pub const TOUCH_SWITCH_WEAPON_CENTER: Vec2 = Vec2::new(WINDOW_SIZE.x - 185.0, WINDOW_SIZE.y - 60.0);
+pub const TOUCH_SWITCH_WEAPON_RADIUS: f32 = 28.0;
+pub const TOUCH_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
+pub const TOUCH_PRESSED_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
+
+// lobby
+pub const LOBBY_SWATCH_SIZE: f32 = 30.0;
+pub const LOBBY_SWATCH_BORDER: f32 = 3.0;
+pub const LOBBY_SELECTED_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
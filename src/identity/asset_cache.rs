@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use bevy::asset::{Asset, Assets, Handle};
+use bevy::math::Quat;
+use bevy::prelude::{
+    Circle, Color, ColorMaterial, Mesh, Rectangle, RegularPolygon, ResMut, Resource,
+};
+use bevy::sprite::Mesh2dHandle;
+
+use crate::constants::{
+    ASTEROID_COLOR, DEGREES_MAX, HALF, PARTICLE_DIMENSION_MULTIPLIER, PARTICLE_FIRE_COLORS,
+    PICKUP_COLORS, PLAYER_FLAME_COLOR, PLAYER_POLYGON_RADIUS, PLAYER_POLYGON_SIDES,
+    PLAYER_PUFF_COLOR, TEAM_COLORS, WALL_COLOR, ZONE_COLOR,
+};
+
+// the shapes everything is drawn with; built up front by handle_asset_cache_setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Player,
+    Square,
+    Circle,
+    Flag,
+    Particle,
+    Flame,
+}
+
+const SHAPES: [Shape; 6] = [
+    Shape::Player,
+    Shape::Square,
+    Shape::Circle,
+    Shape::Flag,
+    Shape::Particle,
+    Shape::Flame,
+];
+
+// one mesh per shape and one material per colour, shared by everything that's spawned, so
+// spawning and despawning doesn't leave a trail of assets behind
+#[derive(Debug, Clone, Default, Resource)]
+pub struct AssetCache {
+    pub meshes: HashMap<String, Mesh2dHandle>,
+    // colours aren't hashable, but there aren't many of them in use at once
+    pub materials: Vec<(Color, Handle<ColorMaterial>)>,
+    // what handle_asset_cache_setup built; kept even while nothing's using them
+    pub kept_meshes: HashSet<String>,
+    pub kept_colors: Vec<Color>,
+}
+
+fn get_shape_key(shape: Shape) -> String {
+    format!("{:?}", shape).to_lowercase()
+}
+
+fn get_shape_mesh(shape: Shape) -> Mesh {
+    match shape {
+        Shape::Player => Mesh::from(RegularPolygon::new(
+            PLAYER_POLYGON_RADIUS,
+            PLAYER_POLYGON_SIDES,
+        )),
+        Shape::Square => Mesh::from(Rectangle::new(1.0, 1.0)),
+        Shape::Circle => Mesh::from(Circle::new(0.5)),
+        Shape::Flag => Mesh::from(RegularPolygon::new(1.0, 3)),
+        Shape::Particle => Mesh::from(Rectangle::new(
+            1.0 * PARTICLE_DIMENSION_MULTIPLIER,
+            1.0 * PARTICLE_DIMENSION_MULTIPLIER,
+        )),
+        // points backwards, out of the back of a ship
+        Shape::Flame => Mesh::from(RegularPolygon::new(0.5, 3))
+            .rotated_by(Quat::from_rotation_z(f32::to_radians(DEGREES_MAX * HALF))),
+    }
+}
+
+// the cache's own handle is the only one left once everything that was using it has gone
+fn is_in_use<A: Asset>(handle: &Handle<A>) -> bool {
+    match handle {
+        Handle::Strong(strong_handle) => Arc::strong_count(strong_handle) > 1,
+        Handle::Weak(_) => true,
+    }
+}
+
+impl AssetCache {
+    pub fn get_mesh(
+        self: &mut AssetCache,
+        shape: Shape,
+        meshes: &mut Assets<Mesh>,
+    ) -> Mesh2dHandle {
+        self.get_mesh_with(&get_shape_key(shape), || get_shape_mesh(shape), meshes)
+    }
+
+    // for shapes that vary (e.g. wall outlines); build is only called the first time key is seen
+    pub fn get_mesh_with(
+        self: &mut AssetCache,
+        key: &str,
+        build: impl FnOnce() -> Mesh,
+        meshes: &mut Assets<Mesh>,
+    ) -> Mesh2dHandle {
+        let mesh = self.meshes.get(key);
+        if mesh.is_some() {
+            return mesh.unwrap().clone();
+        }
+
+        let mesh: Mesh2dHandle = meshes.add(build()).into();
+        self.meshes.insert(key.to_string(), mesh.clone());
+
+        mesh
+    }
+
+    pub fn get_material(
+        self: &mut AssetCache,
+        color: Color,
+        materials: &mut Assets<ColorMaterial>,
+    ) -> Handle<ColorMaterial> {
+        let material = self.materials.iter().find(|(c, _)| *c == color);
+        if material.is_some() {
+            return material.unwrap().1.clone();
+        }
+
+        let material = materials.add(ColorMaterial::from(color));
+        self.materials.push((color, material.clone()));
+
+        material
+    }
+
+    // lets go of anything nothing's using any more (e.g. a player's colour after they've left, or
+    // an asteroid's outline after it's been broken up), so the assets get dropped
+    pub fn release_unused(self: &mut AssetCache) {
+        let kept_meshes = &self.kept_meshes;
+        let kept_colors = &self.kept_colors;
+
+        self.meshes
+            .retain(|key, mesh| kept_meshes.contains(key) || is_in_use(&mesh.0));
+        self.materials
+            .retain(|(color, material)| kept_colors.contains(color) || is_in_use(material));
+    }
+}
+
+pub fn handle_asset_cache_setup(
+    mut asset_cache: ResMut<AssetCache>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for shape in SHAPES {
+        asset_cache.get_mesh(shape, &mut meshes);
+        asset_cache.kept_meshes.insert(get_shape_key(shape));
+    }
+
+    let mut colors = vec![
+        Color::srgb(1.0, 1.0, 1.0),
+        ASTEROID_COLOR,
+        WALL_COLOR,
+        ZONE_COLOR,
+        PLAYER_FLAME_COLOR,
+        PLAYER_PUFF_COLOR,
+    ];
+    colors.extend(PICKUP_COLORS);
+    colors.extend(TEAM_COLORS);
+    colors.extend(PARTICLE_FIRE_COLORS);
+
+    for color in colors {
+        asset_cache.get_material(color, &mut materials);
+        asset_cache.kept_colors.push(color);
+    }
+}
+
+pub fn handle_asset_cache(mut asset_cache: ResMut<AssetCache>) {
+    asset_cache.release_unused();
+}
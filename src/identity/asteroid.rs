@@ -0,0 +1,207 @@
+use std::f32::consts::PI;
+
+use bevy::asset::Assets;
+use bevy::ecs::system::EntityCommands;
+use bevy::ecs::world::EntityRef;
+use bevy::math::{Vec2, Vec3};
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
+};
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::dynamics::RigidBody::Dynamic;
+use bevy_rapier2d::dynamics::{Ccd, Sleeping, Velocity};
+use bevy_rapier2d::geometry::{
+    ActiveEvents, Collider, ColliderMassProperties, Friction, Restitution,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{Collideable, CollisionType};
+use crate::behaviour::moveable::Moveable;
+use crate::client::error::{QuatEMA, Vec2EMA, Vec3EMA, EMA};
+use crate::constants::{
+    ASTEROID_COLOR, ASTEROID_DENSITY, ASTEROID_DIMENSION_MULTIPLIERS,
+    ASTEROID_NETWORK_EMA_SMOOTHING_FACTOR, ASTEROID_NETWORK_UPDATE_RATE_SECONDS,
+    ASTEROID_POLYGON_JITTER, ASTEROID_POLYGON_RADIUS, ASTEROID_POLYGON_SIDES, ASTEROID_SIZE_MAX,
+    FRICTION_COEFFICIENT, MATERIAL_SCALE, RESTITUTION_COEFFICIENT,
+};
+use crate::identity::asset_cache::AssetCache;
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::types::event::{SerializableTransform, SerializableVelocity, SpawnEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Asteroid {
+    pub asteroid_uuid: Uuid,
+    // 1 (smallest) to ASTEROID_SIZE_MAX (largest)
+    pub size: u32,
+    // set by the server once it's been broken up, so it can't be broken up twice
+    pub is_destroyed: bool,
+}
+
+// seeded by the uuid, so the server and every client agree on the shape without it having to go
+// over the wire
+pub fn get_asteroid_vertices(asteroid_uuid: Uuid) -> Vec<Vec2> {
+    let (high, low) = asteroid_uuid.as_u64_pair();
+    let mut rng = StdRng::seed_from_u64(high ^ low);
+
+    let mut vertices = vec![];
+
+    for i in 0..ASTEROID_POLYGON_SIDES {
+        let angle = (i as f32 / ASTEROID_POLYGON_SIDES as f32) * PI * 2.0;
+        let radius = ASTEROID_POLYGON_RADIUS * (1.0 - rng.gen::<f32>() * ASTEROID_POLYGON_JITTER);
+
+        vertices.push(Vec2::new(angle.cos() * radius, angle.sin() * radius));
+    }
+
+    vertices
+}
+
+fn get_asteroid_mesh(vertices: &[Vec2]) -> Mesh {
+    // a fan around the centre (the outline is star-shaped about the origin, so this is safe)
+    let mut positions = vec![[0.0, 0.0, 0.0]];
+    let mut uvs = vec![[0.5, 0.5]];
+
+    for vertex in vertices.iter() {
+        positions.push([vertex.x, vertex.y, 0.0]);
+        uvs.push([vertex.x + 0.5, 0.5 - vertex.y]);
+    }
+
+    let mut indices = vec![];
+    for i in 0..vertices.len() as u32 {
+        indices.extend([0, i + 1, ((i + 1) % vertices.len() as u32) + 1]);
+    }
+
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+pub fn spawn_asteroid(
+    spawn: SpawnEvent,
+    game: &Res<Game>,
+    _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let asteroid_uuid = spawn.entity_uuid;
+    let size = spawn
+        .size
+        .unwrap_or(ASTEROID_SIZE_MAX)
+        .clamp(1, ASTEROID_SIZE_MAX);
+    let color = spawn.color.unwrap();
+    let velocity = spawn.velocity.unwrap().to_velocity();
+
+    let mut transform = spawn.transform.unwrap().to_transform();
+
+    transform.scale = Vec3::splat(MATERIAL_SCALE * ASTEROID_DIMENSION_MULTIPLIERS[size as usize]);
+
+    let vertices = get_asteroid_vertices(asteroid_uuid);
+
+    // one each; the cache lets go of it once the asteroid's gone
+    let mesh = asset_cache.get_mesh_with(
+        &format!("asteroid:{:}", asteroid_uuid),
+        || get_asteroid_mesh(&vertices),
+        meshes,
+    );
+
+    let material = asset_cache.get_material(color, materials);
+
+    let material_mesh = MaterialMesh2dBundle {
+        mesh,
+        transform,
+        material,
+        ..default()
+    };
+
+    let asteroid = Asteroid {
+        asteroid_uuid,
+        size,
+        is_destroyed: false,
+    };
+
+    let moveable = Moveable {
+        entity_uuid: asteroid_uuid,
+        unhandled_updates: vec![],
+        update_to_handle: None,
+        translation_error: Vec3EMA::new(ASTEROID_NETWORK_EMA_SMOOTHING_FACTOR),
+        rotation_error: QuatEMA::new(ASTEROID_NETWORK_EMA_SMOOTHING_FACTOR),
+        linvel_error: Vec2EMA::new(ASTEROID_NETWORK_EMA_SMOOTHING_FACTOR),
+        angvel_error: EMA::new(ASTEROID_NETWORK_EMA_SMOOTHING_FACTOR),
+        update_rate_seconds: ASTEROID_NETWORK_UPDATE_RATE_SECONDS,
+        last_update_handled_at: 0.0,
+        had_rollover: false,
+    };
+
+    let collideable = Collideable {
+        entity_uuid: asteroid_uuid,
+        owner_uuid: None,
+    };
+
+    let mut parent: EntityCommands =
+        commands.spawn((material_mesh, asteroid, moveable, collideable));
+
+    parent
+        .insert(Dynamic)
+        .insert(Sleeping::disabled())
+        .insert(velocity);
+
+    if game.role == "server" {
+        let indices: Vec<[u32; 2]> = (0..vertices.len() as u32)
+            .map(|i| [i, (i + 1) % vertices.len() as u32])
+            .collect();
+
+        parent
+            .insert(Friction::coefficient(FRICTION_COEFFICIENT))
+            .insert(Restitution::coefficient(RESTITUTION_COEFFICIENT))
+            .insert(Ccd::disabled())
+            .insert(Collider::convex_decomposition(&vertices, &indices))
+            .insert(ColliderMassProperties::Density(ASTEROID_DENSITY))
+            .insert(ActiveEvents::all());
+    }
+
+    parent.id()
+}
+
+pub fn despawn_asteroid(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn encode_asteroid(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let asteroid = entity_ref.get::<Asteroid>()?;
+    let transform = entity_ref.get::<Transform>()?;
+    let velocity = entity_ref.get::<Velocity>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: asteroid.asteroid_uuid,
+        entity_type: "asteroid".to_string(),
+        transform: Some(SerializableTransform::from_transform(*transform)),
+        velocity: Some(SerializableVelocity::from_velocity(*velocity)),
+        color: Some(ASTEROID_COLOR),
+        size: Some(asteroid.size),
+        ..Default::default()
+    })
+}
+
+pub fn get_asteroid_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "asteroid".to_string(),
+        spawn: spawn_asteroid,
+        despawn: despawn_asteroid,
+        encode: Some(encode_asteroid),
+        collision_type: Some(CollisionType::Asteroid),
+    }
+}
@@ -0,0 +1,81 @@
+use bevy::asset::Assets;
+use bevy::color::Alpha;
+use bevy::ecs::world::EntityRef;
+use bevy::math::Vec3;
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::constants::{BASE_ALPHA, BASE_RADIUS};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::identity::team::get_team_color;
+use crate::types::event::{SerializableTransform, SpawnEvent};
+
+// where a team keeps its flag and brings the other teams' flags back to
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Base {
+    pub base_uuid: Uuid,
+    pub team: u32,
+}
+
+pub fn spawn_base(
+    spawn: SpawnEvent,
+    _game: &Res<Game>,
+    _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let base_uuid = spawn.entity_uuid;
+    let team = spawn.team.unwrap_or_default();
+
+    let mut transform = spawn.transform.unwrap().to_transform();
+    transform.translation.z = -0.5; // under everything else
+    transform.scale = Vec3::splat(BASE_RADIUS * 2.0);
+
+    let material_mesh = MaterialMesh2dBundle {
+        mesh: asset_cache.get_mesh(Shape::Circle, meshes),
+        material: asset_cache.get_material(get_team_color(team).with_alpha(BASE_ALPHA), materials),
+        transform,
+        ..default()
+    };
+
+    let base = Base { base_uuid, team };
+
+    commands.spawn((material_mesh, base)).id()
+}
+
+pub fn despawn_base(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn encode_base(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let base = entity_ref.get::<Base>()?;
+    let transform = entity_ref.get::<Transform>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: base.base_uuid,
+        entity_type: "base".to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(transform.translation.truncate().extend(0.0)),
+        )),
+        team: Some(base.team),
+        ..Default::default()
+    })
+}
+
+pub fn get_base_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "base".to_string(),
+        spawn: spawn_base,
+        despawn: despawn_base,
+        encode: Some(encode_base),
+        collision_type: None,
+    }
+}
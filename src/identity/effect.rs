@@ -0,0 +1,232 @@
+use bevy::asset::Assets;
+use bevy::ecs::world::EntityRef;
+use bevy::math::Vec3;
+use bevy::prelude::{
+    default, Color, ColorMaterial, Commands, Component, Entity, Event, Mesh, Query, Res, ResMut,
+    Time, Transform,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::behaviour::expireable::Expireable;
+use crate::constants::{
+    EFFECT_FIRE_RATE_MULTIPLIER, EFFECT_FIRE_RATE_SECONDS, EFFECT_SHIELD_AMOUNT,
+    EFFECT_SHIELD_SECONDS, EFFECT_SPEED_MULTIPLIER, EFFECT_SPEED_SECONDS, EFFECT_STUN_SECONDS,
+    MATERIAL_SCALE, PLAYER_HEIGHT_MULTIPLIER,
+};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::types::event::SpawnEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stacking {
+    // applying it again replaces the one that's there (so the timer starts over)
+    Refresh,
+    // applying it again adds another, up to stacks_max (replacing the oldest after that)
+    Stack,
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectRule {
+    pub effect_type: &'static str,
+    pub seconds: f64,
+    // what it starts with; shield points for "shield", a multiplier for "speed" / "fire_rate"
+    pub magnitude: f32,
+    pub stacking: Stacking,
+    pub stacks_max: usize,
+    // drawn around the player on the client
+    pub color: Color,
+    pub radius_multiplier: f32,
+}
+
+pub const EFFECT_RULES: [EffectRule; 4] = [
+    EffectRule {
+        effect_type: "shield",
+        seconds: EFFECT_SHIELD_SECONDS,
+        magnitude: EFFECT_SHIELD_AMOUNT,
+        stacking: Stacking::Refresh,
+        stacks_max: 1,
+        color: Color::srgba(0.3, 0.6, 1.0, 0.35),
+        radius_multiplier: 1.4,
+    },
+    EffectRule {
+        effect_type: "speed",
+        seconds: EFFECT_SPEED_SECONDS,
+        magnitude: EFFECT_SPEED_MULTIPLIER,
+        stacking: Stacking::Stack,
+        stacks_max: 2,
+        color: Color::srgba(1.0, 1.0, 0.3, 0.2),
+        radius_multiplier: 1.2,
+    },
+    EffectRule {
+        effect_type: "fire_rate",
+        seconds: EFFECT_FIRE_RATE_SECONDS,
+        magnitude: EFFECT_FIRE_RATE_MULTIPLIER,
+        stacking: Stacking::Refresh,
+        stacks_max: 1,
+        color: Color::srgba(1.0, 0.4, 1.0, 0.2),
+        radius_multiplier: 1.1,
+    },
+    EffectRule {
+        effect_type: "stun",
+        seconds: EFFECT_STUN_SECONDS,
+        magnitude: 1.0,
+        stacking: Stacking::Refresh,
+        stacks_max: 1,
+        color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+        radius_multiplier: 1.0,
+    },
+];
+
+pub fn get_effect_rule(effect_type: &str) -> Option<EffectRule> {
+    EFFECT_RULES
+        .iter()
+        .find(|effect_rule| effect_rule.effect_type == effect_type)
+        .cloned()
+}
+
+// a timed effect on a player; kept as its own entity so it can expire through Expireable
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Effect {
+    pub effect_uuid: Uuid,
+    pub player_uuid: Uuid,
+    // one of the EFFECT_RULES
+    pub effect_type: String,
+    pub magnitude: f32,
+    pub applied_at: f64,
+}
+
+// server side; the stacking rules are applied by whatever handles these
+#[derive(Event, Debug, Clone)]
+pub struct ApplyEffectEvent {
+    pub player_uuid: Uuid,
+    pub effect_type: String,
+}
+
+// everything the active effects on a player add up to
+#[derive(Debug, Clone)]
+pub struct EffectModifiers {
+    pub speed_multiplier: f32,
+    pub fire_rate_multiplier: f32,
+    pub is_stunned: bool,
+    pub is_shielded: bool,
+}
+
+pub fn get_effect_modifiers<'a>(
+    effects: impl Iterator<Item = &'a Effect>,
+    player_uuid: Uuid,
+) -> EffectModifiers {
+    let mut effect_modifiers = EffectModifiers {
+        speed_multiplier: 1.0,
+        fire_rate_multiplier: 1.0,
+        is_stunned: false,
+        is_shielded: false,
+    };
+
+    for effect in effects {
+        if effect.player_uuid != player_uuid {
+            continue;
+        }
+
+        if effect.effect_type == "speed" {
+            effect_modifiers.speed_multiplier *= effect.magnitude;
+        } else if effect.effect_type == "fire_rate" {
+            effect_modifiers.fire_rate_multiplier *= effect.magnitude;
+        } else if effect.effect_type == "stun" {
+            effect_modifiers.is_stunned = true;
+        } else if effect.effect_type == "shield" && effect.magnitude > 0.0 {
+            effect_modifiers.is_shielded = true;
+        }
+    }
+
+    effect_modifiers
+}
+
+// soaks up as much of the damage as the player's shields can take; returns what gets through
+pub fn absorb_damage(effect_query: &mut Query<&mut Effect>, player_uuid: Uuid, amount: f32) -> f32 {
+    let mut amount = amount;
+
+    for mut effect in effect_query.iter_mut() {
+        if effect.player_uuid != player_uuid || effect.effect_type != "shield" {
+            continue;
+        }
+
+        let absorbed = amount.min(effect.magnitude);
+        effect.magnitude -= absorbed;
+        amount -= absorbed;
+    }
+
+    amount
+}
+
+pub fn spawn_effect(
+    spawn: SpawnEvent,
+    game: &Res<Game>,
+    time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let effect_uuid = spawn.entity_uuid;
+    let effect_type = spawn.pickup_type.unwrap_or_default();
+    let effect_rule = get_effect_rule(&effect_type).unwrap_or(EFFECT_RULES[0].clone());
+
+    let expireable = Expireable {
+        entity_uuid: effect_uuid,
+        expires_at: time.elapsed_seconds_f64() + effect_rule.seconds,
+    };
+
+    let effect = Effect {
+        effect_uuid,
+        player_uuid: spawn.player_uuid.unwrap_or_default(),
+        effect_type: effect_rule.effect_type.to_string(),
+        magnitude: spawn.magnitude.unwrap_or(effect_rule.magnitude),
+        applied_at: time.elapsed_seconds_f64(),
+    };
+
+    if game.role == "server" {
+        return commands.spawn((effect, expireable)).id();
+    }
+
+    // the client draws it; it's moved onto the player by handle_effect_visual
+    let material_mesh = MaterialMesh2dBundle {
+        mesh: asset_cache.get_mesh(Shape::Circle, meshes),
+        material: asset_cache.get_material(effect_rule.color, materials),
+        transform: Transform::from_scale(Vec3::splat(
+            MATERIAL_SCALE * PLAYER_HEIGHT_MULTIPLIER * effect_rule.radius_multiplier,
+        )),
+        ..default()
+    };
+
+    commands.spawn((material_mesh, effect, expireable)).id()
+}
+
+pub fn despawn_effect(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn encode_effect(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let effect = entity_ref.get::<Effect>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: effect.effect_uuid,
+        entity_type: "effect".to_string(),
+        pickup_type: Some(effect.effect_type.clone()),
+        player_uuid: Some(effect.player_uuid),
+        magnitude: Some(effect.magnitude),
+        ..Default::default()
+    })
+}
+
+pub fn get_effect_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "effect".to_string(),
+        spawn: spawn_effect,
+        despawn: despawn_effect,
+        encode: Some(encode_effect),
+        collision_type: None,
+    }
+}
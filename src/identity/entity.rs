@@ -1,11 +1,25 @@
+use std::collections::HashMap;
+
 use bevy::asset::Assets;
-use bevy::prelude::{ColorMaterial, Commands, Component, Entity, Mesh, Query, Res, ResMut, Time};
+use bevy::ecs::world::EntityRef;
+use bevy::prelude::{
+    warn, ColorMaterial, Commands, Component, Entity, Mesh, Query, Res, ResMut, Resource, Time,
+};
 use serde::{Deserialize, Serialize};
 
+use crate::behaviour::collideable::{get_collision_groups, CollisionType};
+use crate::behaviour::identifiable::Identifiable;
+use crate::identity::asset_cache::AssetCache;
+use crate::identity::asteroid::get_asteroid_kind;
+use crate::identity::base::get_base_kind;
+use crate::identity::effect::get_effect_kind;
+use crate::identity::flag::get_flag_kind;
 use crate::identity::game::Game;
-use crate::identity::particle::{despawn_particle, spawn_particle, Particle};
-use crate::identity::player::{despawn_player, spawn_player, Player};
-use crate::identity::projectile::{despawn_projectile, spawn_projectile, Projectile};
+use crate::identity::pickup::get_pickup_kind;
+use crate::identity::player::get_player_kind;
+use crate::identity::projectile::get_projectile_kind;
+use crate::identity::wall::{get_boundary_kind, get_wall_kind};
+use crate::identity::zone::get_zone_kind;
 use crate::types::event::{DespawnEvent, SpawnEvent};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
@@ -14,82 +28,155 @@ pub struct Local {}
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
 pub struct Remote {}
 
+pub type SpawnFn = fn(
+    SpawnEvent,
+    &Res<Game>,
+    Time,
+    &mut ResMut<AssetCache>,
+    &mut ResMut<'_, Assets<Mesh>>,
+    &mut ResMut<Assets<ColorMaterial>>,
+    &mut Commands,
+) -> Entity;
+
+pub type DespawnFn = fn(Entity, Time, &mut Commands);
+
+// the spawn event that would recreate an entity that's already around (e.g. for a late joiner)
+pub type EncodeFn = fn(&EntityRef) -> Option<SpawnEvent>;
+
+// everything the dispatchers need to know about a kind of entity; entity_type is what goes over
+// the wire in spawn / despawn events and what collisions are reported as
+#[derive(Debug, Clone)]
+pub struct EntityKind {
+    pub entity_type: String,
+    pub spawn: SpawnFn,
+    pub despawn: DespawnFn,
+    // None for kinds that don't outlive the moment (or aren't sent on their own)
+    pub encode: Option<EncodeFn>,
+    // None for kinds that don't collide with anything
+    pub collision_type: Option<CollisionType>,
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct EntityKinds {
+    pub kinds: HashMap<String, EntityKind>,
+}
+
+impl EntityKinds {
+    pub fn register(self: &mut EntityKinds, kind: EntityKind) {
+        self.kinds.insert(kind.entity_type.clone(), kind);
+    }
+
+    pub fn get(self: &EntityKinds, entity_type: &str) -> Option<&EntityKind> {
+        self.kinds.get(entity_type)
+    }
+
+    pub fn get_collision_type(self: &EntityKinds, entity_type: &str) -> Option<CollisionType> {
+        self.get(entity_type).and_then(|kind| kind.collision_type)
+    }
+}
+
+pub fn get_entity_kinds() -> EntityKinds {
+    let mut entity_kinds = EntityKinds {
+        kinds: HashMap::new(),
+    };
+
+    entity_kinds.register(get_player_kind());
+    entity_kinds.register(get_projectile_kind());
+    entity_kinds.register(get_asteroid_kind());
+    entity_kinds.register(get_pickup_kind());
+    entity_kinds.register(get_effect_kind());
+    entity_kinds.register(get_flag_kind());
+    entity_kinds.register(get_base_kind());
+    entity_kinds.register(get_zone_kind());
+    entity_kinds.register(get_wall_kind());
+    entity_kinds.register(get_boundary_kind());
+
+    entity_kinds
+}
+
 pub fn spawn_entity(
     spawn: SpawnEvent,
+    entity_kinds: &Res<EntityKinds>,
     game: &Res<Game>,
-    player_query: &Query<&Player>,
-    projectile_query: &Query<&Projectile>,
-    particle_query: &Query<&Particle>,
+    identifiable_query: &Query<(Entity, &Identifiable)>,
     time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
     meshes: &mut ResMut<'_, Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     commands: &mut Commands,
 ) {
-    if spawn.entity_type == "player" {
-        spawn_player(
-            spawn.entity_uuid,
-            game,
-            player_query,
-            spawn.color.unwrap(),
-            spawn.transform.unwrap().to_transform(),
-            spawn.velocity.unwrap().to_velocity(),
-            time,
-            meshes,
-            materials,
-            commands,
-        );
-    } else if spawn.entity_type == "projectile" {
-        spawn_projectile(
-            spawn.entity_uuid,
-            game,
-            projectile_query,
-            spawn.color.unwrap(),
-            spawn.transform.unwrap().to_transform(),
-            spawn.velocity.unwrap().to_velocity(),
-            time,
-            meshes,
-            materials,
-            commands,
-        );
-    } else if spawn.entity_type == "particle" {
-        spawn_particle(
-            spawn.entity_uuid,
-            game,
-            particle_query,
-            spawn.color.unwrap(),
-            spawn.transform.unwrap().to_transform(),
-            spawn.velocity.unwrap().to_velocity(),
-            time,
-            meshes,
-            materials,
-            commands,
-        );
-    } else {
-        panic!(
-            "unsupported spawn.entity_type={:?} for spawn={:?}",
-            spawn.entity_type, spawn
+    // it came over the network, so it could be anything
+    let kind = entity_kinds.get(&spawn.entity_type);
+    if kind.is_none() {
+        warn!(
+            "spawn_entity; ignoring unsupported spawn.entity_type={:?}",
+            spawn.entity_type
         );
+        return;
+    }
+
+    let kind = kind.unwrap();
+
+    for (_, identifiable) in identifiable_query.iter() {
+        if identifiable.entity_uuid == spawn.entity_uuid {
+            return; // spawn not required
+        }
+    }
+
+    let identifiable = Identifiable {
+        entity_uuid: spawn.entity_uuid,
+        entity_type: spawn.entity_type.clone(),
+    };
+
+    let entity = (kind.spawn)(spawn, game, time, asset_cache, meshes, materials, commands);
+
+    commands.entity(entity).insert(identifiable);
+
+    if kind.collision_type.is_some() {
+        commands
+            .entity(entity)
+            .insert(get_collision_groups(kind.collision_type.unwrap()));
     }
 }
 
 pub fn despawn_entity(
     despawn: DespawnEvent,
-    player_query: &Query<(Entity, &Player)>,
-    projectile_query: &Query<(Entity, &Projectile)>,
-    particle_query: &Query<(Entity, &Particle)>,
+    entity_kinds: &Res<EntityKinds>,
+    identifiable_query: &Query<(Entity, &Identifiable)>,
     time: Time,
     commands: &mut Commands,
 ) {
-    if despawn.entity_type == "player" {
-        despawn_player(despawn.entity_uuid, player_query, time, commands)
-    } else if despawn.entity_type == "projectile" {
-        despawn_projectile(despawn.entity_uuid, projectile_query, time, commands)
-    } else if despawn.entity_type == "particle" {
-        despawn_particle(despawn.entity_uuid, particle_query, time, commands)
-    } else {
-        panic!(
-            "unsupported despawn.entity_type={:?} for despawn={:?}",
-            despawn.entity_type, despawn
+    let kind = entity_kinds.get(&despawn.entity_type);
+    if kind.is_none() {
+        warn!(
+            "despawn_entity; ignoring unsupported despawn.entity_type={:?}",
+            despawn.entity_type
         );
+        return;
+    }
+
+    for (entity, identifiable) in identifiable_query.iter() {
+        if identifiable.entity_uuid != despawn.entity_uuid {
+            continue;
+        }
+
+        if commands.get_entity(entity).is_none() {
+            continue;
+        }
+
+        (kind.unwrap().despawn)(entity, time, commands);
     }
 }
+
+pub fn encode_entity(
+    entity_kinds: &Res<EntityKinds>,
+    identifiable: &Identifiable,
+    entity_ref: &EntityRef,
+) -> Option<SpawnEvent> {
+    let kind = entity_kinds.get(&identifiable.entity_type);
+    if kind.is_none() || kind.unwrap().encode.is_none() {
+        return None;
+    }
+
+    (kind.unwrap().encode.unwrap())(entity_ref)
+}
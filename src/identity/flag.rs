@@ -0,0 +1,106 @@
+use bevy::asset::Assets;
+use bevy::ecs::world::EntityRef;
+use bevy::math::{Vec2, Vec3};
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::constants::{FLAG_DIMENSION_MULTIPLIER, MATERIAL_SCALE};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::identity::team::get_team_color;
+use crate::types::event::{SerializableTransform, SpawnEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Flag {
+    pub flag_uuid: Uuid,
+    pub team: u32,
+    // where it sits (at the team's base) when nobody has it
+    pub home: Vec2,
+    pub carrier_uuid: Option<Uuid>,
+    // set while it's lying around after the carrier died
+    pub dropped_at: Option<f64>,
+}
+
+impl Flag {
+    pub fn is_at_home(self: &Flag) -> bool {
+        self.carrier_uuid.is_none() && self.dropped_at.is_none()
+    }
+
+    pub fn drop(self: &mut Flag, now: f64) {
+        self.carrier_uuid = None;
+        self.dropped_at = Some(now);
+    }
+
+    pub fn return_home(self: &mut Flag) {
+        self.carrier_uuid = None;
+        self.dropped_at = None;
+    }
+}
+
+pub fn spawn_flag(
+    spawn: SpawnEvent,
+    _game: &Res<Game>,
+    _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let flag_uuid = spawn.entity_uuid;
+    let team = spawn.team.unwrap_or_default();
+
+    let mut transform = spawn.transform.unwrap().to_transform();
+    transform.translation.z = 0.75; // above the players, so it's visible while carried
+    transform.scale = Vec3::splat(MATERIAL_SCALE * FLAG_DIMENSION_MULTIPLIER);
+
+    let material_mesh = MaterialMesh2dBundle {
+        mesh: asset_cache.get_mesh(Shape::Flag, meshes),
+        material: asset_cache.get_material(get_team_color(team), materials),
+        transform,
+        ..default()
+    };
+
+    let flag = Flag {
+        flag_uuid,
+        team,
+        home: transform.translation.truncate(),
+        carrier_uuid: None,
+        dropped_at: None,
+    };
+
+    commands.spawn((material_mesh, flag)).id()
+}
+
+pub fn despawn_flag(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+// sent at home; where it actually is comes with the next game mode event
+pub fn encode_flag(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let flag = entity_ref.get::<Flag>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: flag.flag_uuid,
+        entity_type: "flag".to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(flag.home.extend(0.0)),
+        )),
+        team: Some(flag.team),
+        ..Default::default()
+    })
+}
+
+pub fn get_flag_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "flag".to_string(),
+        spawn: spawn_flag,
+        despawn: despawn_flag,
+        encode: Some(encode_flag),
+        collision_type: None,
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 
+use bevy::math::Vec2;
 use bevy::prelude::Resource;
 use uuid::Uuid;
 
@@ -11,4 +12,9 @@ pub struct Game {
     pub last_update: f64,
     pub server_time_at_join: f64,
     pub client_time_at_join: f64,
+    pub is_connected: bool,
+    pub last_ping_at: f64,
+    pub round_trip_time: Option<f64>,
+    pub is_wrapping: bool,
+    pub arena_size: Vec2,
 }
@@ -0,0 +1,90 @@
+use bevy::prelude::Resource;
+
+use crate::constants::{FLAG_CAPTURE_LIMIT, ZONE_HOLD_LIMIT_SECONDS};
+
+#[derive(Debug, Clone)]
+pub struct GameMode {
+    pub game_mode: &'static str,
+    // modes with objectives are always played in teams
+    pub is_team_only: bool,
+    // what each team's objective is counted in; None if the score limit decides the round
+    pub objective_name: Option<&'static str>,
+    // the first team to reach this wins the round
+    pub objective_limit: f32,
+}
+
+pub const GAME_MODES: [GameMode; 3] = [
+    GameMode {
+        game_mode: "deathmatch",
+        is_team_only: false,
+        objective_name: None,
+        objective_limit: 0.0,
+    },
+    // take the other team's flag back to your base while your own flag is at home
+    GameMode {
+        game_mode: "ctf",
+        is_team_only: true,
+        objective_name: Some("captures"),
+        objective_limit: FLAG_CAPTURE_LIMIT,
+    },
+    // hold the zone in the middle with nobody from another team in it
+    GameMode {
+        game_mode: "koth",
+        is_team_only: true,
+        objective_name: Some("seconds held"),
+        objective_limit: ZONE_HOLD_LIMIT_SECONDS,
+    },
+];
+
+pub fn get_game_mode(game_mode: &str) -> GameMode {
+    GAME_MODES
+        .iter()
+        .find(|known_game_mode| known_game_mode.game_mode == game_mode)
+        .unwrap_or(&GAME_MODES[0])
+        .clone()
+}
+
+// server-side progress towards the objective, indexed by team
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Objectives {
+    pub scores: Vec<f32>,
+    // the team that has the zone to itself (if anybody)
+    pub zone_team: Option<u32>,
+    pub last_update_at: f64,
+}
+
+impl Objectives {
+    pub fn reset(self: &mut Objectives, team_count: u32) {
+        self.scores = vec![0.0; team_count as usize];
+        self.zone_team = None;
+    }
+
+    pub fn add(self: &mut Objectives, team: u32, amount: f32) {
+        if (team as usize) < self.scores.len() {
+            self.scores[team as usize] += amount;
+        }
+    }
+
+    // the team that's ahead; None on a tie
+    pub fn get_leader(self: &Objectives) -> Option<u32> {
+        let mut leader = None;
+        let mut best = f32::MIN;
+        let mut is_tied = false;
+
+        for (team, score) in self.scores.iter().enumerate() {
+            if *score > best {
+                leader = Some(team as u32);
+                best = *score;
+                is_tied = false;
+            } else if *score == best {
+                is_tied = true;
+            }
+        }
+
+        if is_tied {
+            return None;
+        }
+
+        leader
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{BOUNDS, HALF, MAP_EDGE_THICKNESS};
+
+fn get_default_size() -> Vec2 {
+    BOUNDS
+}
+
+fn get_default_edges() -> String {
+    "wrap".to_string()
+}
+
+// loaded from a JSON file on the server (see the maps folder); everything is in arena coordinates,
+// with 0, 0 in the middle
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct Map {
+    pub name: String,
+    // width and height, independent of the window (the camera follows the player around bigger ones)
+    #[serde(default = "get_default_size")]
+    pub size: Vec2,
+    // "wrap" (leave one side, come back on the other) or "solid"
+    #[serde(default = "get_default_edges")]
+    pub edges: String,
+    // each one a convex outline
+    #[serde(default)]
+    pub walls: Vec<Vec<Vec2>>,
+    // players spawn at one of these (or anywhere clear if there are none)
+    #[serde(default)]
+    pub spawn_points: Vec<Vec2>,
+    // pickups spawn at one of these (or anywhere clear if there are none)
+    #[serde(default)]
+    pub pickup_spawners: Vec<Vec2>,
+}
+
+impl Default for Map {
+    fn default() -> Map {
+        Map {
+            name: "arena".to_string(),
+            size: get_default_size(),
+            edges: get_default_edges(),
+            walls: vec![],
+            spawn_points: vec![],
+            pickup_spawners: vec![],
+        }
+    }
+}
+
+fn get_distance_to_segment(point: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((point - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+
+    point.distance(a + ab * t)
+}
+
+fn is_inside(point: Vec2, outline: &[Vec2]) -> bool {
+    let mut sign = 0.0;
+
+    for i in 0..outline.len() {
+        let a = outline[i];
+        let b = outline[(i + 1) % outline.len()];
+        let cross = (b - a).perp_dot(point - a);
+
+        if cross == 0.0 {
+            continue;
+        }
+
+        if sign == 0.0 {
+            sign = cross.signum();
+        } else if cross.signum() != sign {
+            return false;
+        }
+    }
+
+    true
+}
+
+impl Map {
+    pub fn is_solid(self: &Map) -> bool {
+        self.edges == "solid"
+    }
+
+    // walls just outside the arena (if the edges are solid) that everything bounces off
+    pub fn get_edge_walls(self: &Map) -> Vec<Vec<Vec2>> {
+        let mut walls = vec![];
+
+        if !self.is_solid() {
+            return walls;
+        }
+
+        let inner = self.size * HALF;
+        let outer = inner + Vec2::splat(MAP_EDGE_THICKNESS);
+
+        walls.push(vec![
+            Vec2::new(-outer.x, inner.y),
+            Vec2::new(outer.x, inner.y),
+            Vec2::new(outer.x, outer.y),
+            Vec2::new(-outer.x, outer.y),
+        ]);
+        walls.push(vec![
+            Vec2::new(-outer.x, -outer.y),
+            Vec2::new(outer.x, -outer.y),
+            Vec2::new(outer.x, -inner.y),
+            Vec2::new(-outer.x, -inner.y),
+        ]);
+        walls.push(vec![
+            Vec2::new(-outer.x, -inner.y),
+            Vec2::new(-inner.x, -inner.y),
+            Vec2::new(-inner.x, inner.y),
+            Vec2::new(-outer.x, inner.y),
+        ]);
+        walls.push(vec![
+            Vec2::new(inner.x, -inner.y),
+            Vec2::new(outer.x, -inner.y),
+            Vec2::new(outer.x, inner.y),
+            Vec2::new(inner.x, inner.y),
+        ]);
+
+        walls
+    }
+
+    // whether something of this radius would fit here without overlapping a wall
+    pub fn is_clear(self: &Map, point: Vec2, radius: f32) -> bool {
+        for outline in self.walls.iter().chain(self.get_edge_walls().iter()) {
+            if outline.is_empty() {
+                continue;
+            }
+
+            if is_inside(point, outline) {
+                return false;
+            }
+
+            for i in 0..outline.len() {
+                let a = outline[i];
+                let b = outline[(i + 1) % outline.len()];
+
+                if get_distance_to_segment(point, a, b) < radius {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
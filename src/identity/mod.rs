@@ -1,5 +1,18 @@
+pub mod asset_cache;
+pub mod asteroid;
+pub mod base;
+pub mod effect;
 pub mod entity;
+pub mod flag;
 pub mod game;
-pub mod particle;
+pub mod game_mode;
+pub mod map;
+pub mod pickup;
 pub mod player;
 pub mod projectile;
+pub mod round;
+pub mod scoreboard;
+pub mod team;
+pub mod wall;
+pub mod weapon;
+pub mod zone;
@@ -0,0 +1,123 @@
+use bevy::asset::Assets;
+use bevy::ecs::system::EntityCommands;
+use bevy::ecs::world::EntityRef;
+use bevy::math::{Quat, Vec3};
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::dynamics::RigidBody::Fixed;
+use bevy_rapier2d::geometry::{ActiveEvents, Collider, Sensor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{Collideable, CollisionType};
+use crate::behaviour::expireable::Expireable;
+use crate::constants::{
+    MATERIAL_SCALE, PICKUP_COLORS, PICKUP_DIMENSION_MULTIPLIER, PICKUP_EXPIRY_SECONDS, PICKUP_TYPES,
+};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::types::event::{SerializableTransform, SpawnEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Pickup {
+    pub pickup_uuid: Uuid,
+    // one of PICKUP_TYPES
+    pub pickup_type: String,
+    // set by the server once somebody's had it, so it can't be had twice
+    pub is_collected: bool,
+}
+
+pub fn spawn_pickup(
+    spawn: SpawnEvent,
+    game: &Res<Game>,
+    time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let pickup_uuid = spawn.entity_uuid;
+    let pickup_type = spawn.pickup_type.unwrap_or_default();
+
+    let pickup_index = PICKUP_TYPES
+        .iter()
+        .position(|known_pickup_type| *known_pickup_type == pickup_type)
+        .unwrap_or(0);
+
+    let mut transform = spawn.transform.unwrap().to_transform();
+
+    // diamonds, so they don't look like projectiles
+    transform.rotation = Quat::from_rotation_z(f32::to_radians(45.0));
+    transform.scale = Vec3::splat(MATERIAL_SCALE * PICKUP_DIMENSION_MULTIPLIER);
+
+    let mesh = asset_cache.get_mesh(Shape::Square, meshes);
+
+    let material = asset_cache.get_material(PICKUP_COLORS[pickup_index], materials);
+
+    let material_mesh = MaterialMesh2dBundle {
+        mesh,
+        transform,
+        material,
+        ..default()
+    };
+
+    let pickup = Pickup {
+        pickup_uuid,
+        pickup_type: PICKUP_TYPES[pickup_index].to_string(),
+        is_collected: false,
+    };
+
+    let collideable = Collideable {
+        entity_uuid: pickup_uuid,
+        owner_uuid: None,
+    };
+
+    let expireable = Expireable {
+        entity_uuid: pickup_uuid,
+        expires_at: time.elapsed_seconds_f64() + PICKUP_EXPIRY_SECONDS,
+    };
+
+    let mut parent: EntityCommands =
+        commands.spawn((material_mesh, pickup, collideable, expireable));
+
+    parent.insert(Fixed);
+
+    if game.role == "server" {
+        parent
+            .insert(Collider::cuboid(0.5, 0.5))
+            .insert(Sensor)
+            .insert(ActiveEvents::all());
+    }
+
+    parent.id()
+}
+
+pub fn despawn_pickup(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn encode_pickup(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let pickup = entity_ref.get::<Pickup>()?;
+    let transform = entity_ref.get::<Transform>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: pickup.pickup_uuid,
+        entity_type: "pickup".to_string(),
+        transform: Some(SerializableTransform::from_transform(*transform)),
+        pickup_type: Some(pickup.pickup_type.clone()),
+        ..Default::default()
+    })
+}
+
+pub fn get_pickup_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "pickup".to_string(),
+        spawn: spawn_pickup,
+        despawn: despawn_pickup,
+        encode: Some(encode_pickup),
+        collision_type: Some(CollisionType::Pickup),
+    }
+}
@@ -1,62 +1,63 @@
 use bevy::asset::Assets;
 use bevy::ecs::system::EntityCommands;
+use bevy::ecs::world::EntityRef;
 use bevy::math::Vec3;
 use bevy::prelude::{
-    default, Color, ColorMaterial, Commands, Component, Entity, Mesh, Query, RegularPolygon, Res,
-    ResMut, Time, Transform,
+    default, Color, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
 };
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_rapier2d::dynamics::RigidBody::Dynamic;
-use bevy_rapier2d::dynamics::{Ccd, Damping, Sleeping};
+use bevy_rapier2d::dynamics::{Ccd, Damping, Sleeping, Velocity};
 use bevy_rapier2d::geometry::{
     ActiveEvents, Collider, ColliderMassProperties, Friction, Restitution,
 };
-use bevy_rapier2d::prelude::Velocity;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::behaviour::collideable::Collideable;
+use crate::behaviour::collideable::{Collideable, CollisionType};
+use crate::behaviour::damageable::Damageable;
 use crate::behaviour::moveable::Moveable;
 use crate::behaviour::weaponized::Weaponized;
 use crate::client::error::{QuatEMA, Vec2EMA, Vec3EMA, EMA};
 use crate::constants::{
     FRICTION_COEFFICIENT, MATERIAL_SCALE, PLAYER_ANGULAR_DAMPING, PLAYER_COLLIDER_BALL_RADIUS,
-    PLAYER_DENSITY, PLAYER_HEIGHT_MULTIPLIER, PLAYER_LINEAR_DAMPING,
-    PLAYER_NETWORK_EMA_SMOOTHING_FACTOR, PLAYER_NETWORK_UPDATE_RATE_SECONDS, PLAYER_POLYGON_RADIUS,
-    PLAYER_POLYGON_SIDES, PLAYER_WIDTH_MULTIPLIER, RESTITUTION_COEFFICIENT,
+    PLAYER_DENSITY, PLAYER_HEALTH_MAX, PLAYER_HEIGHT_MULTIPLIER, PLAYER_LINEAR_DAMPING,
+    PLAYER_NETWORK_EMA_SMOOTHING_FACTOR, PLAYER_NETWORK_UPDATE_RATE_SECONDS,
+    PLAYER_WIDTH_MULTIPLIER, RESTITUTION_COEFFICIENT,
 };
-use crate::identity::entity::{Local, Remote};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::entity::{EntityKind, Local, Remote};
 use crate::identity::game::Game;
-use crate::types::event::InputEvent;
+use crate::types::event::{InputEvent, SerializableTransform, SerializableVelocity, SpawnEvent};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
 pub struct Player {
     pub player_uuid: Uuid,
+    pub name: String,
     pub color: Color,
     pub is_local_player: bool,
     pub unhandled_inputs: Vec<InputEvent>,
     pub last_input: Option<InputEvent>,
+    pub score: i32,
+    pub ping: Option<f64>,
+    pub team: Option<u32>,
 }
 
 pub fn spawn_player(
-    player_uuid: Uuid,
+    spawn: SpawnEvent,
     game: &Res<Game>,
-    player_query: &Query<&Player>,
-    color: Color,
-    transform: Transform,
-    velocity: Velocity,
     _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
     meshes: &mut ResMut<'_, Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     commands: &mut Commands,
-) {
-    for player in player_query.iter() {
-        if player.player_uuid == player_uuid {
-            return; // spawn not required
-        }
-    }
+) -> Entity {
+    let player_uuid = spawn.entity_uuid;
+    let name = spawn.name.unwrap_or_default();
+    let color = spawn.color.unwrap();
+    let velocity = spawn.velocity.unwrap().to_velocity();
 
-    let mut transform = transform;
+    let mut transform = spawn.transform.unwrap().to_transform();
 
     let mut size = Vec3::splat(MATERIAL_SCALE);
     size.y *= PLAYER_HEIGHT_MULTIPLIER;
@@ -64,14 +65,9 @@ pub fn spawn_player(
 
     transform.scale = size;
 
-    let mesh = meshes
-        .add(Mesh::from(RegularPolygon::new(
-            PLAYER_POLYGON_RADIUS,
-            PLAYER_POLYGON_SIDES,
-        )))
-        .into();
+    let mesh = asset_cache.get_mesh(Shape::Player, meshes);
 
-    let material = materials.add(ColorMaterial::from(color));
+    let material = asset_cache.get_material(color, materials);
 
     let material_mesh = MaterialMesh2dBundle {
         mesh,
@@ -85,10 +81,14 @@ pub fn spawn_player(
 
     let player = Player {
         player_uuid,
+        name,
         color,
         is_local_player,
         unhandled_inputs: vec![],
         last_input: None,
+        score: 0,
+        ping: None,
+        team: spawn.team,
     };
 
     let moveable = Moveable {
@@ -106,17 +106,28 @@ pub fn spawn_player(
 
     let collideable = Collideable {
         entity_uuid: player_uuid,
+        owner_uuid: None,
+    };
+
+    let damageable = Damageable {
+        entity_uuid: player_uuid,
+        health: PLAYER_HEALTH_MAX,
+        max_health: PLAYER_HEALTH_MAX,
     };
 
     let mut parent: EntityCommands;
 
     if is_local_player {
-        parent = commands.spawn((material_mesh, player, moveable, collideable, Local {}));
+        parent = commands.spawn((
+            material_mesh,
+            player,
+            moveable,
+            collideable,
+            damageable,
+            Local {},
+        ));
     } else {
-        let weaponized = Weaponized {
-            weapon_uuid: Uuid::new_v4(),
-            last_fired_at: 0.0,
-        };
+        let weaponized = Weaponized::new(Uuid::new_v4());
 
         parent = commands.spawn((
             material_mesh,
@@ -124,6 +135,7 @@ pub fn spawn_player(
             moveable,
             weaponized,
             collideable,
+            damageable,
             Remote {},
         ));
     }
@@ -146,24 +158,37 @@ pub fn spawn_player(
             .insert(ColliderMassProperties::Density(PLAYER_DENSITY))
             .insert(ActiveEvents::all());
     }
+
+    parent.id()
 }
 
-pub fn despawn_player(
-    player_uuid: Uuid,
-    player_query: &Query<(Entity, &Player)>,
-    _time: Time,
-    commands: &mut Commands,
-) {
-    for (entity, player) in player_query.iter() {
-        if player.player_uuid != player_uuid {
-            continue;
-        }
-
-        let entity_commands = commands.get_entity(entity);
-        if entity_commands.is_none() {
-            continue;
-        }
-
-        entity_commands.unwrap().despawn();
+pub fn despawn_player(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn encode_player(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let player = entity_ref.get::<Player>()?;
+    let transform = entity_ref.get::<Transform>()?;
+    let velocity = entity_ref.get::<Velocity>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: player.player_uuid,
+        entity_type: "player".to_string(),
+        transform: Some(SerializableTransform::from_transform(*transform)),
+        velocity: Some(SerializableVelocity::from_velocity(*velocity)),
+        color: Some(player.color),
+        name: Some(player.name.clone()),
+        team: player.team,
+        ..Default::default()
+    })
+}
+
+pub fn get_player_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "player".to_string(),
+        spawn: spawn_player,
+        despawn: despawn_player,
+        encode: Some(encode_player),
+        collision_type: Some(CollisionType::Player),
     }
 }
@@ -2,69 +2,79 @@ use bevy::asset::Assets;
 use bevy::ecs::system::EntityCommands;
 use bevy::math::{Vec2, Vec3};
 use bevy::prelude::{
-    default, Color, ColorMaterial, Commands, Component, Entity, Mesh, Query, Rectangle, Res,
-    ResMut, Time, Transform,
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Rectangle, Res, ResMut, Time,
 };
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_rapier2d::dynamics::RigidBody::Dynamic;
-use bevy_rapier2d::dynamics::{Ccd, Sleeping, Velocity};
+use bevy_rapier2d::dynamics::{Ccd, Sleeping};
 use bevy_rapier2d::geometry::{
-    ActiveEvents, Collider, ColliderMassProperties, Friction, Restitution,
+    ActiveEvents, ActiveHooks, Collider, ColliderMassProperties, Friction, Restitution,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::behaviour::collideable::Collideable;
+use crate::behaviour::collideable::{Collideable, CollisionType};
 use crate::behaviour::expireable::Expireable;
 use crate::behaviour::moveable::Moveable;
 use crate::client::error::{QuatEMA, Vec2EMA, Vec3EMA, EMA};
 use crate::constants::{
-    FRICTION_COEFFICIENT, MATERIAL_SCALE, PROEJCTILE_DIMENSION_MULTIPLIER, PROJECTILE_DENSITY,
-    PROJECTILE_EXPIRY_SECONDS, PROJECTILE_NETWORK_EMA_SMOOTHING_FACTOR,
-    PROJECTILE_NETWORK_UPDATE_RATE_SECONDS, RESTITUTION_COEFFICIENT,
+    FRICTION_COEFFICIENT, MATERIAL_SCALE, PROJECTILE_DENSITY,
+    PROJECTILE_NETWORK_EMA_SMOOTHING_FACTOR, PROJECTILE_NETWORK_UPDATE_RATE_SECONDS,
+    RESTITUTION_COEFFICIENT,
 };
+use crate::identity::asset_cache::AssetCache;
+use crate::identity::entity::EntityKind;
 use crate::identity::game::Game;
+use crate::identity::weapon::get_weapon;
+use crate::types::event::SpawnEvent;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Component)]
 pub struct Projectile {
     pub projectile_uuid: Uuid,
     pub weapon_uuid: Uuid,
+    pub weapon_type: String,
+    pub charge: f32,
 }
 
 pub fn spawn_projectile(
-    projectile_uuid: Uuid,
+    spawn: SpawnEvent,
     game: &Res<Game>,
-    projectile_query: &Query<&Projectile>,
-    color: Color,
-    transform: Transform,
-    velocity: Velocity,
     time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
     meshes: &mut ResMut<'_, Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
     commands: &mut Commands,
-) {
-    for projectile in projectile_query.iter() {
-        if projectile.projectile_uuid == projectile_uuid {
-            return; // spawn not required
-        }
-    }
-
-    let mut transform = transform;
+) -> Entity {
+    let projectile_uuid = spawn.entity_uuid;
+    let weapon_uuid = spawn.weapon_uuid.unwrap_or_default();
+    let owner_uuid = spawn.player_uuid;
+    let weapon = get_weapon(&spawn.weapon_type.unwrap_or_default());
+    let charge = spawn.charge.unwrap_or(1.0);
+    let projectile_size = weapon.get_size(charge);
+    let color = spawn.color.unwrap();
+    let velocity = spawn.velocity.unwrap().to_velocity();
+
+    let mut transform = spawn.transform.unwrap().to_transform();
 
     let mut size = Vec3::splat(MATERIAL_SCALE);
-    size.y *= PROEJCTILE_DIMENSION_MULTIPLIER;
-    size.x *= PROEJCTILE_DIMENSION_MULTIPLIER;
+    size.y *= projectile_size;
+    size.x *= projectile_size;
 
     transform.scale = size;
 
-    let rect = Rectangle::from_size(Vec2::new(
-        1.0 * PROEJCTILE_DIMENSION_MULTIPLIER,
-        1.0 * PROEJCTILE_DIMENSION_MULTIPLIER,
-    ));
+    // charged shots come in all sizes; near enough is good enough for sharing
+    let mesh = asset_cache.get_mesh_with(
+        &format!("projectile:{:.2}", projectile_size),
+        || {
+            Mesh::from(Rectangle::from_size(Vec2::new(
+                1.0 * projectile_size,
+                1.0 * projectile_size,
+            )))
+        },
+        meshes,
+    );
 
-    let mesh = meshes.add(Mesh::from(rect)).into();
-
-    let material = materials.add(ColorMaterial::from(color));
+    let material = asset_cache.get_material(color, materials);
 
     let material_mesh = MaterialMesh2dBundle {
         mesh,
@@ -75,7 +85,9 @@ pub fn spawn_projectile(
 
     let projectile = Projectile {
         projectile_uuid,
-        weapon_uuid: Default::default(),
+        weapon_uuid,
+        weapon_type: weapon.weapon_type.to_string(),
+        charge,
     };
 
     let moveable = Moveable {
@@ -93,11 +105,12 @@ pub fn spawn_projectile(
 
     let collideable = Collideable {
         entity_uuid: projectile_uuid,
+        owner_uuid,
     };
 
     let expireable = Expireable {
         entity_uuid: projectile_uuid,
-        expires_at: time.elapsed_seconds_f64() + PROJECTILE_EXPIRY_SECONDS,
+        expires_at: time.elapsed_seconds_f64() + weapon.projectile_lifetime_seconds,
     };
 
     let mut parent: EntityCommands =
@@ -114,30 +127,27 @@ pub fn spawn_projectile(
             .insert(Restitution::coefficient(RESTITUTION_COEFFICIENT))
             .insert(Ccd::disabled())
             .insert(Collider::cuboid(
-                1.0 * PROEJCTILE_DIMENSION_MULTIPLIER,
-                1.0 * PROEJCTILE_DIMENSION_MULTIPLIER,
+                1.0 * projectile_size,
+                1.0 * projectile_size,
             ))
             .insert(ColliderMassProperties::Density(PROJECTILE_DENSITY))
-            .insert(ActiveEvents::all());
+            .insert(ActiveEvents::all())
+            .insert(ActiveHooks::FILTER_CONTACT_PAIRS);
     }
+
+    parent.id()
 }
 
-pub fn despawn_projectile(
-    projectile_uuid: Uuid,
-    projectile_query: &Query<(Entity, &Projectile)>,
-    _time: Time,
-    commands: &mut Commands,
-) {
-    for (entity, projectile) in projectile_query.iter() {
-        if projectile.projectile_uuid != projectile_uuid {
-            continue;
-        }
-
-        let entity_commands = commands.get_entity(entity);
-        if entity_commands.is_none() {
-            continue;
-        }
-
-        entity_commands.unwrap().despawn();
+pub fn despawn_projectile(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn get_projectile_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "projectile".to_string(),
+        spawn: spawn_projectile,
+        despawn: despawn_projectile,
+        encode: None,
+        collision_type: Some(CollisionType::Projectile),
     }
 }
@@ -0,0 +1,44 @@
+use bevy::prelude::{Resource, States};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(States, Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MatchState {
+    #[default]
+    WaitingForPlayers,
+    Countdown,
+    InRound,
+    Results,
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct MatchRules {
+    pub min_players: usize,
+    pub countdown_seconds: f64,
+    // None for no time limit
+    pub round_duration_seconds: Option<f64>,
+    // None for no score limit
+    pub score_limit: Option<i32>,
+    pub results_seconds: f64,
+    // fewer than 2 for free-for-all
+    pub team_count: u32,
+    // whether teammates can hurt each other
+    pub is_friendly_fire: bool,
+    // one of the game modes in identity::game_mode
+    pub game_mode: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+pub struct Round {
+    pub match_state: MatchState,
+    pub round_number: u32,
+    pub started_at: f64,
+    // in server time; None if this state only ends when something happens
+    pub ends_at: Option<f64>,
+    pub min_players: usize,
+    pub score_limit: Option<i32>,
+    // None until the results are in (or if it was a draw)
+    pub winner_uuid: Option<Uuid>,
+    // as above, but for team rounds
+    pub winner_team: Option<u32>,
+}
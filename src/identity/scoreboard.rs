@@ -0,0 +1,10 @@
+use bevy::prelude::Resource;
+
+use crate::types::event::{ScoreboardEntry, TeamScoreboardEntry};
+
+#[derive(Debug, Clone, Resource)]
+pub struct Scoreboard {
+    pub entries: Vec<ScoreboardEntry>,
+    pub teams: Vec<TeamScoreboardEntry>,
+    pub last_update_at: f64,
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::Color;
+
+use crate::constants::{TEAM_COLORS, TEAM_NAMES};
+use crate::identity::player::Player;
+use crate::types::event::TeamScoreboardEntry;
+
+pub fn get_team_name(team: u32) -> String {
+    TEAM_NAMES[team as usize % TEAM_NAMES.len()].to_string()
+}
+
+pub fn get_team_color(team: u32) -> Color {
+    TEAM_COLORS[team as usize % TEAM_COLORS.len()]
+}
+
+// free-for-all players have no team, so they're nobody's teammate
+pub fn is_teammate(team_a: Option<u32>, team_b: Option<u32>) -> bool {
+    team_a.is_some() && team_a == team_b
+}
+
+// the team with the fewest players (lowest numbered on a tie); None if there are no teams
+pub fn get_balanced_team(team_count: u32, teams: &[Option<u32>]) -> Option<u32> {
+    if team_count < 2 {
+        return None;
+    }
+
+    (0..team_count).min_by_key(|team| teams.iter().filter(|t| **t == Some(*team)).count())
+}
+
+pub fn get_team_scores<'a>(
+    team_count: u32,
+    players: impl Iterator<Item = &'a Player>,
+) -> Vec<TeamScoreboardEntry> {
+    if team_count < 2 {
+        return vec![];
+    }
+
+    let mut entries: Vec<TeamScoreboardEntry> = (0..team_count)
+        .map(|team| TeamScoreboardEntry {
+            team,
+            name: get_team_name(team),
+            color: get_team_color(team),
+            score: 0,
+        })
+        .collect();
+
+    for player in players {
+        if player.team.is_none() || player.team.unwrap() >= team_count {
+            continue;
+        }
+
+        entries[player.team.unwrap() as usize].score += player.score;
+    }
+
+    entries
+}
@@ -0,0 +1,181 @@
+use bevy::asset::Assets;
+use bevy::ecs::system::EntityCommands;
+use bevy::ecs::world::EntityRef;
+use bevy::math::Vec2;
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
+};
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::dynamics::RigidBody::Fixed;
+use bevy_rapier2d::geometry::{CoefficientCombineRule, Collider, Friction, Restitution};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{Collideable, CollisionType};
+use crate::behaviour::identifiable::Identifiable;
+use crate::constants::{
+    BOUNDARY_RESTITUTION_COEFFICIENT, FRICTION_COEFFICIENT, RESTITUTION_COEFFICIENT, WALL_COLOR,
+};
+use crate::identity::asset_cache::AssetCache;
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::types::event::{SerializableTransform, SpawnEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Wall {
+    pub wall_uuid: Uuid,
+    // convex, relative to the wall's transform
+    pub points: Vec<Vec2>,
+}
+
+// a triangle fan, which is fine for convex outlines
+fn get_wall_mesh(points: &[Vec2]) -> Mesh {
+    let positions: Vec<[f32; 3]> = points.iter().map(|point| [point.x, point.y, 0.0]).collect();
+    let normals: Vec<[f32; 3]> = points.iter().map(|_| [0.0, 0.0, 1.0]).collect();
+    let uvs: Vec<[f32; 2]> = points.iter().map(|_| [0.0, 0.0]).collect();
+
+    let mut indices = vec![];
+    for i in 1..points.len().saturating_sub(1) {
+        indices.extend_from_slice(&[0, i as u32, i as u32 + 1]);
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+fn spawn_wall_or_boundary(
+    spawn: SpawnEvent,
+    is_boundary: bool,
+    game: &Res<Game>,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let wall_uuid = spawn.entity_uuid;
+    let points = spawn.points.unwrap_or_default();
+
+    let mut transform = spawn.transform.unwrap().to_transform();
+    transform.translation.z = -0.25; // under the ships, over the bases
+
+    let material_mesh = MaterialMesh2dBundle {
+        mesh: asset_cache.get_mesh_with(
+            &format!("wall:{:?}", points),
+            || get_wall_mesh(&points),
+            meshes,
+        ),
+        material: asset_cache.get_material(WALL_COLOR, materials),
+        transform,
+        ..default()
+    };
+
+    let wall = Wall {
+        wall_uuid,
+        points: points.clone(),
+    };
+
+    let collideable = Collideable {
+        entity_uuid: wall_uuid,
+        owner_uuid: None,
+    };
+
+    let mut parent: EntityCommands = commands.spawn((material_mesh, wall, collideable));
+
+    parent.insert(Fixed);
+
+    if game.role == "server" {
+        let collider = Collider::convex_hull(&points);
+
+        if collider.is_some() {
+            parent
+                .insert(collider.unwrap())
+                .insert(Friction::coefficient(FRICTION_COEFFICIENT));
+        }
+
+        // the boundary decides how bouncy it is, whatever hits it
+        if is_boundary {
+            parent.insert(Restitution {
+                coefficient: BOUNDARY_RESTITUTION_COEFFICIENT,
+                combine_rule: CoefficientCombineRule::Max,
+            });
+        } else {
+            parent.insert(Restitution::coefficient(RESTITUTION_COEFFICIENT));
+        }
+    }
+
+    parent.id()
+}
+
+pub fn spawn_wall(
+    spawn: SpawnEvent,
+    game: &Res<Game>,
+    _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    spawn_wall_or_boundary(spawn, false, game, asset_cache, meshes, materials, commands)
+}
+
+pub fn spawn_boundary(
+    spawn: SpawnEvent,
+    game: &Res<Game>,
+    _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    spawn_wall_or_boundary(spawn, true, game, asset_cache, meshes, materials, commands)
+}
+
+// walls and boundaries look the same on the wire, apart from the entity_type
+pub fn encode_wall(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let wall = entity_ref.get::<Wall>()?;
+    let identifiable = entity_ref.get::<Identifiable>()?;
+    let transform = entity_ref.get::<Transform>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: wall.wall_uuid,
+        entity_type: identifiable.entity_type.clone(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(transform.translation.truncate().extend(0.0)),
+        )),
+        points: Some(wall.points.clone()),
+        ..Default::default()
+    })
+}
+
+pub fn despawn_wall(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn get_wall_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "wall".to_string(),
+        spawn: spawn_wall,
+        despawn: despawn_wall,
+        encode: Some(encode_wall),
+        collision_type: Some(CollisionType::Wall),
+    }
+}
+
+// the walls around the arena when its edges are solid
+pub fn get_boundary_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "boundary".to_string(),
+        spawn: spawn_boundary,
+        despawn: despawn_wall,
+        encode: Some(encode_wall),
+        collision_type: Some(CollisionType::Boundary),
+    }
+}
@@ -0,0 +1,173 @@
+use bevy::prelude::Color;
+
+use crate::constants::{
+    PROEJCTILE_DIMENSION_MULTIPLIER, PROJECTILE_DAMAGE, PROJECTILE_EXPIRY_SECONDS,
+    PROJECTILE_LINEAR_VELOCITY, WEAPON_CHARGE_MIN, WEAPON_FIRE_RATE_SECONDS,
+};
+
+#[derive(Debug, Clone)]
+pub struct Weapon {
+    // what goes over the wire and into Weaponized.weapon_types
+    pub weapon_type: &'static str,
+    pub fire_rate_seconds: f64,
+    pub projectile_count: u32,
+    // total angle the projectiles are fanned out across
+    pub spread_degrees: f32,
+    pub projectile_speed: f32,
+    pub projectile_lifetime_seconds: f64,
+    pub projectile_damage: f32,
+    pub projectile_size: f32,
+    pub color: Color,
+    // if set, holding fire charges the weapon (up to this long) and releasing fires it
+    pub charge_seconds: Option<f64>,
+    // if set, projectiles turn towards the nearest enemy at this many degrees per second
+    pub homing_degrees_per_second: Option<f32>,
+    // dropped behind the ship rather than fired ahead of it
+    pub is_dropped: bool,
+    // if set, the weapon holds this many shots and has to reload once they're gone
+    pub magazine_size: Option<u32>,
+    pub reload_seconds: f64,
+    // if set, each shot adds this much (out of 1.0) to the heat gauge
+    pub heat_per_shot: Option<f32>,
+    // if set, players hit by it get this effect (see EFFECT_RULES) as well as the damage
+    pub effect_on_hit: Option<&'static str>,
+}
+
+impl Weapon {
+    // charge is 0.0 to 1.0 for charged weapons and always 1.0 for everything else
+    pub fn get_charge_multiplier(self: &Weapon, charge: f32) -> f32 {
+        if self.charge_seconds.is_none() {
+            return 1.0;
+        }
+
+        WEAPON_CHARGE_MIN + (1.0 - WEAPON_CHARGE_MIN) * charge.clamp(0.0, 1.0)
+    }
+
+    pub fn get_damage(self: &Weapon, charge: f32) -> f32 {
+        self.projectile_damage * self.get_charge_multiplier(charge)
+    }
+
+    pub fn get_size(self: &Weapon, charge: f32) -> f32 {
+        self.projectile_size * self.get_charge_multiplier(charge)
+    }
+}
+
+pub const WEAPONS: [Weapon; 6] = [
+    Weapon {
+        weapon_type: "blaster",
+        fire_rate_seconds: WEAPON_FIRE_RATE_SECONDS,
+        projectile_count: 1,
+        spread_degrees: 0.0,
+        projectile_speed: PROJECTILE_LINEAR_VELOCITY,
+        projectile_lifetime_seconds: PROJECTILE_EXPIRY_SECONDS,
+        projectile_damage: PROJECTILE_DAMAGE,
+        projectile_size: PROEJCTILE_DIMENSION_MULTIPLIER,
+        color: Color::srgb(0.0, 1.0, 1.0),
+        charge_seconds: None,
+        homing_degrees_per_second: None,
+        is_dropped: false,
+        magazine_size: None,
+        reload_seconds: 0.0,
+        heat_per_shot: Some(0.1),
+        effect_on_hit: None,
+    },
+    Weapon {
+        weapon_type: "spread",
+        fire_rate_seconds: 0.6,
+        projectile_count: 5,
+        spread_degrees: 40.0,
+        projectile_speed: PROJECTILE_LINEAR_VELOCITY * 0.85,
+        projectile_lifetime_seconds: 1.0,
+        projectile_damage: 12.0,
+        projectile_size: PROEJCTILE_DIMENSION_MULTIPLIER * 0.8,
+        color: Color::srgb(1.0, 0.55, 0.0),
+        charge_seconds: None,
+        homing_degrees_per_second: None,
+        is_dropped: false,
+        magazine_size: Some(6),
+        reload_seconds: 2.0,
+        heat_per_shot: None,
+        effect_on_hit: None,
+    },
+    Weapon {
+        weapon_type: "rapid",
+        fire_rate_seconds: 0.08,
+        projectile_count: 1,
+        spread_degrees: 6.0,
+        projectile_speed: PROJECTILE_LINEAR_VELOCITY * 1.2,
+        projectile_lifetime_seconds: 1.5,
+        projectile_damage: 8.0,
+        projectile_size: PROEJCTILE_DIMENSION_MULTIPLIER * 0.6,
+        color: Color::srgb(1.0, 1.0, 0.3),
+        charge_seconds: None,
+        homing_degrees_per_second: None,
+        is_dropped: false,
+        magazine_size: None,
+        reload_seconds: 0.0,
+        heat_per_shot: Some(0.06),
+        effect_on_hit: None,
+    },
+    Weapon {
+        weapon_type: "charged",
+        fire_rate_seconds: 0.5,
+        projectile_count: 1,
+        spread_degrees: 0.0,
+        projectile_speed: PROJECTILE_LINEAR_VELOCITY * 1.5,
+        projectile_lifetime_seconds: PROJECTILE_EXPIRY_SECONDS,
+        projectile_damage: 80.0,
+        projectile_size: PROEJCTILE_DIMENSION_MULTIPLIER * 3.0,
+        color: Color::srgb(0.7, 0.3, 1.0),
+        charge_seconds: Some(1.5),
+        homing_degrees_per_second: None,
+        is_dropped: false,
+        magazine_size: None,
+        reload_seconds: 0.0,
+        heat_per_shot: None,
+        effect_on_hit: None,
+    },
+    Weapon {
+        weapon_type: "mine",
+        fire_rate_seconds: 1.0,
+        projectile_count: 1,
+        spread_degrees: 0.0,
+        projectile_speed: 0.0,
+        projectile_lifetime_seconds: 20.0,
+        projectile_damage: 50.0,
+        projectile_size: PROEJCTILE_DIMENSION_MULTIPLIER * 2.0,
+        color: Color::srgb(1.0, 0.2, 0.2),
+        charge_seconds: None,
+        homing_degrees_per_second: None,
+        is_dropped: true,
+        magazine_size: Some(3),
+        reload_seconds: 5.0,
+        heat_per_shot: None,
+        effect_on_hit: Some("stun"),
+    },
+    Weapon {
+        weapon_type: "homing",
+        fire_rate_seconds: 0.9,
+        projectile_count: 1,
+        spread_degrees: 0.0,
+        projectile_speed: PROJECTILE_LINEAR_VELOCITY * 0.6,
+        projectile_lifetime_seconds: 4.0,
+        projectile_damage: 30.0,
+        projectile_size: PROEJCTILE_DIMENSION_MULTIPLIER * 1.2,
+        color: Color::srgb(0.3, 1.0, 0.3),
+        charge_seconds: None,
+        homing_degrees_per_second: Some(180.0),
+        is_dropped: false,
+        magazine_size: Some(4),
+        reload_seconds: 3.0,
+        heat_per_shot: None,
+        effect_on_hit: None,
+    },
+];
+
+// falls back to the first weapon for anything unrecognised (e.g. from an older client)
+pub fn get_weapon(weapon_type: &str) -> Weapon {
+    WEAPONS
+        .iter()
+        .find(|weapon| weapon.weapon_type == weapon_type)
+        .unwrap_or(&WEAPONS[0])
+        .clone()
+}
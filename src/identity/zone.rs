@@ -0,0 +1,98 @@
+use bevy::asset::Assets;
+use bevy::ecs::world::EntityRef;
+use bevy::math::Vec3;
+use bevy::prelude::{
+    default, ColorMaterial, Commands, Component, Entity, Mesh, Res, ResMut, Time, Transform,
+};
+use bevy::sprite::MaterialMesh2dBundle;
+use bevy_rapier2d::geometry::{ActiveEvents, Collider, Sensor};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{Collideable, CollisionType};
+use crate::constants::{ZONE_COLOR, ZONE_RADIUS};
+use crate::identity::asset_cache::{AssetCache, Shape};
+use crate::identity::entity::EntityKind;
+use crate::identity::game::Game;
+use crate::types::event::{SerializableTransform, SpawnEvent};
+
+// the hill in king-of-the-hill; a sensor on the server, which keeps track of who's in it
+#[derive(Debug, Clone, Serialize, Deserialize, Component)]
+pub struct Zone {
+    pub zone_uuid: Uuid,
+    // the players inside it right now
+    pub occupant_uuids: Vec<Uuid>,
+}
+
+pub fn spawn_zone(
+    spawn: SpawnEvent,
+    game: &Res<Game>,
+    _time: Time,
+    asset_cache: &mut ResMut<AssetCache>,
+    meshes: &mut ResMut<'_, Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    commands: &mut Commands,
+) -> Entity {
+    let zone_uuid = spawn.entity_uuid;
+
+    let mut transform = spawn.transform.unwrap().to_transform();
+    transform.translation.z = -0.5; // under everything else
+    transform.scale = Vec3::splat(ZONE_RADIUS * 2.0);
+
+    // recoloured by handle_zone_visual as it changes hands
+    let material_mesh = MaterialMesh2dBundle {
+        mesh: asset_cache.get_mesh(Shape::Circle, meshes),
+        material: asset_cache.get_material(ZONE_COLOR, materials),
+        transform,
+        ..default()
+    };
+
+    let zone = Zone {
+        zone_uuid,
+        occupant_uuids: vec![],
+    };
+
+    let collideable = Collideable {
+        entity_uuid: zone_uuid,
+        owner_uuid: None,
+    };
+
+    let mut parent = commands.spawn((material_mesh, zone, collideable));
+
+    if game.role == "server" {
+        parent
+            .insert(Collider::ball(0.5))
+            .insert(Sensor)
+            .insert(ActiveEvents::all());
+    }
+
+    parent.id()
+}
+
+pub fn despawn_zone(entity: Entity, _time: Time, commands: &mut Commands) {
+    commands.entity(entity).despawn();
+}
+
+pub fn encode_zone(entity_ref: &EntityRef) -> Option<SpawnEvent> {
+    let zone = entity_ref.get::<Zone>()?;
+    let transform = entity_ref.get::<Transform>()?;
+
+    Some(SpawnEvent {
+        entity_uuid: zone.zone_uuid,
+        entity_type: "zone".to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(transform.translation.truncate().extend(0.0)),
+        )),
+        ..Default::default()
+    })
+}
+
+pub fn get_zone_kind() -> EntityKind {
+    EntityKind {
+        entity_type: "zone".to_string(),
+        spawn: spawn_zone,
+        despawn: despawn_zone,
+        encode: Some(encode_zone),
+        collision_type: Some(CollisionType::Zone),
+    }
+}
@@ -1,5 +1,5 @@
 use bevy::log::trace;
-use bevy::prelude::{App, FixedUpdate, Startup};
+use bevy::prelude::{in_state, App, FixedUpdate, IntoSystemConfigs, OnEnter, Startup};
 
 use crate::base::app::{
     get_base_app, AfterNetworkTransition1, AfterNetworkTransition2, AfterNetworkTransition3,
@@ -9,16 +9,46 @@ use crate::base::rollover::handle_rollover_for_moveable;
 use crate::behaviour::collideable::handle_rapier_collision_event;
 use crate::behaviour::expireable::handle_expireable;
 use crate::behaviour::weaponized::handle_fire_event;
+use crate::identity::effect::ApplyEffectEvent;
+use crate::identity::game_mode::Objectives;
+use crate::identity::round::MatchState;
+use crate::server::asteroid::{
+    handle_asteroid_wave, handle_collision_event_for_asteroid, handle_round_reset_for_asteroid,
+    AsteroidWaves,
+};
 use crate::server::collision::handle_collision_event;
+use crate::server::damage::{handle_collision_event_for_damageable, handle_kill_event_for_player};
 use crate::server::despawn::handle_despawn_event;
+use crate::server::effect::{handle_apply_effect_event, handle_effect, handle_effect_event};
+use crate::server::game_mode::{
+    handle_collision_event_for_zone, handle_flag, handle_game_mode_event,
+    handle_kill_event_for_flag, handle_objectives, handle_round_reset_for_ctf,
+    handle_round_reset_for_game_mode, handle_round_reset_for_koth, handle_zone, is_game_mode,
+};
+use crate::server::ghost::handle_ghost_for_collideable;
+use crate::server::hello::handle_hello_event;
 use crate::server::input::{handle_input_event, handle_input_for_player};
 use crate::server::join::handle_join_event;
+use crate::server::kill::handle_kill_event;
 use crate::server::leave::handle_leave_event;
+use crate::server::map::{get_map, handle_collision_event_for_wall, handle_map_setup};
 use crate::server::moveable::handle_update_for_moveable;
-use crate::server::network::{handle_close_event, handle_open_event, handle_websocket_server};
+use crate::server::network::{handle_close_event, handle_websocket_server};
+use crate::server::pickup::{
+    handle_collision_event_for_pickup, handle_pickup_spawner, handle_round_reset_for_pickup,
+    PickupSpawner,
+};
+use crate::server::ping::handle_ping_event;
+use crate::server::projectile::handle_homing_projectile;
+use crate::server::round::{
+    get_match_rules, handle_join_event_for_round, handle_match_state_transition, handle_round,
+    handle_round_event, handle_round_reset,
+};
+use crate::server::scoreboard::{handle_scoreboard, handle_scoreboard_event};
 use crate::server::setup::handle_setup;
 use crate::server::spawn::handle_spawn_event;
 use crate::server::update::handle_update_event;
+use crate::server::weapon::{handle_weapon_event, handle_weaponized};
 use crate::server::websocket::get_websocket_server;
 
 pub fn get_app_for_server() -> App {
@@ -31,7 +61,25 @@ pub fn get_app_for_server() -> App {
         web_socket_server
     );
 
+    app.insert_resource(get_match_rules());
+
+    app.insert_resource(get_map());
+
+    app.insert_resource(AsteroidWaves {
+        wave_number: 0,
+        last_cleared_at: 0.0,
+    });
+
+    app.insert_resource(PickupSpawner {
+        last_spawned_at: 0.0,
+    });
+
+    app.insert_resource(Objectives::default());
+
+    app.add_event::<ApplyEffectEvent>();
+
     app.add_systems(Startup, handle_setup);
+    app.add_systems(Startup, handle_map_setup);
 
     // the server side implementation of the WebSocket
     app.insert_non_send_resource(web_socket_server);
@@ -40,9 +88,10 @@ pub fn get_app_for_server() -> App {
     app.add_systems(NetworkTransition, handle_websocket_server);
 
     // handlers to wire game events together
-    app.add_systems(AfterNetworkTransition1, handle_open_event);
+    app.add_systems(AfterNetworkTransition1, handle_hello_event);
     app.add_systems(AfterNetworkTransition1, handle_close_event);
     app.add_systems(AfterNetworkTransition2, handle_join_event);
+    app.add_systems(AfterNetworkTransition2, handle_join_event_for_round);
     app.add_systems(AfterNetworkTransition2, handle_leave_event);
     app.add_systems(AfterNetworkTransition3, handle_spawn_event);
     app.add_systems(AfterNetworkTransition3, handle_despawn_event);
@@ -53,12 +102,88 @@ pub fn get_app_for_server() -> App {
     app.add_systems(AfterNetworkTransition4, handle_fire_event);
     app.add_systems(AfterNetworkTransition4, handle_rapier_collision_event);
     app.add_systems(AfterNetworkTransition4, handle_collision_event);
+    app.add_systems(
+        AfterNetworkTransition4,
+        handle_collision_event_for_damageable.run_if(in_state(MatchState::InRound)),
+    );
+    app.add_systems(AfterNetworkTransition4, handle_collision_event_for_asteroid);
+    app.add_systems(AfterNetworkTransition4, handle_collision_event_for_pickup);
+    app.add_systems(AfterNetworkTransition4, handle_collision_event_for_wall);
+    app.add_systems(AfterNetworkTransition4, handle_apply_effect_event);
+    app.add_systems(AfterNetworkTransition4, handle_kill_event_for_player);
+    app.add_systems(AfterNetworkTransition4, handle_kill_event);
+    app.add_systems(AfterNetworkTransition4, handle_ping_event);
+    app.add_systems(AfterNetworkTransition4, handle_scoreboard_event);
+    app.add_systems(AfterNetworkTransition4, handle_match_state_transition);
+    app.add_systems(AfterNetworkTransition4, handle_round_event);
+    app.add_systems(AfterNetworkTransition4, handle_weapon_event);
+    app.add_systems(
+        AfterNetworkTransition4,
+        handle_kill_event_for_flag.run_if(is_game_mode("ctf")),
+    );
+    app.add_systems(
+        AfterNetworkTransition4,
+        handle_collision_event_for_zone.run_if(is_game_mode("koth")),
+    );
+    app.add_systems(AfterNetworkTransition4, handle_game_mode_event);
+    app.add_systems(AfterNetworkTransition4, handle_effect_event);
+
+    // handlers for the match lifecycle
+    app.add_systems(OnEnter(MatchState::Countdown), handle_round_reset);
+    app.add_systems(
+        OnEnter(MatchState::Countdown),
+        handle_round_reset_for_asteroid,
+    );
+    app.add_systems(
+        OnEnter(MatchState::Countdown),
+        handle_round_reset_for_pickup,
+    );
+    app.add_systems(
+        OnEnter(MatchState::Countdown),
+        handle_round_reset_for_game_mode,
+    );
+    app.add_systems(
+        OnEnter(MatchState::Countdown),
+        handle_round_reset_for_ctf
+            .after(handle_round_reset_for_game_mode)
+            .run_if(is_game_mode("ctf")),
+    );
+    app.add_systems(
+        OnEnter(MatchState::Countdown),
+        handle_round_reset_for_koth
+            .after(handle_round_reset_for_game_mode)
+            .run_if(is_game_mode("koth")),
+    );
 
     // handlers to calculate game state per time step
     app.add_systems(FixedUpdate, handle_input_for_player);
+    app.add_systems(FixedUpdate, handle_homing_projectile);
+    app.add_systems(FixedUpdate, handle_weaponized);
     app.add_systems(FixedUpdate, handle_rollover_for_moveable);
+    app.add_systems(
+        FixedUpdate,
+        handle_ghost_for_collideable.after(handle_rollover_for_moveable),
+    );
     app.add_systems(FixedUpdate, handle_update_for_moveable);
     app.add_systems(FixedUpdate, handle_expireable);
+    app.add_systems(FixedUpdate, handle_scoreboard);
+    app.add_systems(FixedUpdate, handle_round);
+    app.add_systems(FixedUpdate, handle_asteroid_wave);
+    app.add_systems(FixedUpdate, handle_pickup_spawner);
+    app.add_systems(FixedUpdate, handle_effect);
+    app.add_systems(
+        FixedUpdate,
+        handle_flag
+            .run_if(in_state(MatchState::InRound))
+            .run_if(is_game_mode("ctf")),
+    );
+    app.add_systems(
+        FixedUpdate,
+        handle_zone
+            .run_if(in_state(MatchState::InRound))
+            .run_if(is_game_mode("koth")),
+    );
+    app.add_systems(FixedUpdate, handle_objectives);
 
     trace!("client.get_app(); returning app={:?}", app);
 
@@ -0,0 +1,283 @@
+use bevy::math::{Quat, Vec2, Vec3};
+use bevy::prelude::{
+    EventReader, EventWriter, Query, Res, ResMut, Resource, State, Time, Transform,
+};
+use bevy_rapier2d::dynamics::Velocity;
+use rand::{thread_rng, Rng};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{Collider, CollisionEvent, CollisionType};
+use crate::behaviour::damageable::Damageable;
+use crate::behaviour::weaponized::Weaponized;
+use crate::constants::{
+    ASTEROID_ANGULAR_VELOCITY_MAX, ASTEROID_COLOR, ASTEROID_DAMAGES,
+    ASTEROID_DIMENSION_MULTIPLIERS, ASTEROID_LINEAR_VELOCITY, ASTEROID_SCORES, ASTEROID_SIZE_MAX,
+    ASTEROID_SPLIT_ANGLE_DEGREES, ASTEROID_SPLIT_COUNT, ASTEROID_SPLIT_VELOCITY_MULTIPLIER,
+    ASTEROID_WAVE_DELAY_SECONDS, ASTEROID_WAVE_SIZE, ASTEROID_WAVE_SIZE_INCREMENT,
+    ASTEROID_WAVE_SIZE_MAX, DEGREES_MAX, HALF, MATERIAL_SCALE,
+};
+use crate::identity::asteroid::Asteroid;
+use crate::identity::effect::{absorb_damage, Effect};
+use crate::identity::map::Map;
+use crate::identity::player::Player;
+use crate::identity::projectile::Projectile;
+use crate::identity::round::MatchState;
+use crate::types::event::{
+    DespawnEvent, KillEvent, SerializableTransform, SerializableVelocity, SpawnEvent,
+};
+
+#[derive(Debug, Clone, Resource)]
+pub struct AsteroidWaves {
+    pub wave_number: u32,
+    pub last_cleared_at: f64,
+}
+
+fn get_spawn_event_for_asteroid(size: u32, transform: Transform, velocity: Velocity) -> SpawnEvent {
+    SpawnEvent {
+        entity_uuid: Uuid::new_v4(),
+        entity_type: "asteroid".to_string(),
+        transform: Some(SerializableTransform::from_transform(transform)),
+        velocity: Some(SerializableVelocity::from_velocity(velocity)),
+        color: Some(ASTEROID_COLOR),
+        size: Some(size),
+        ..Default::default()
+    }
+}
+
+fn get_random_angvel() -> f32 {
+    (thread_rng().gen::<f32>() * 2.0 - 1.0) * ASTEROID_ANGULAR_VELOCITY_MAX
+}
+
+pub fn handle_asteroid_wave(
+    time: Res<Time>,
+    map: Res<Map>,
+    mut asteroid_waves: ResMut<AsteroidWaves>,
+    asteroid_query: Query<&Asteroid>,
+    mut spawn_event_writer: EventWriter<SpawnEvent>,
+) {
+    if !asteroid_query.is_empty() {
+        asteroid_waves.last_cleared_at = time.elapsed_seconds_f64();
+        return;
+    }
+
+    if time.elapsed_seconds_f64() - asteroid_waves.last_cleared_at < ASTEROID_WAVE_DELAY_SECONDS {
+        return;
+    }
+
+    let mut rng = thread_rng();
+
+    let wave_size = (ASTEROID_WAVE_SIZE
+        + asteroid_waves.wave_number * ASTEROID_WAVE_SIZE_INCREMENT)
+        .min(ASTEROID_WAVE_SIZE_MAX);
+
+    // far enough in from solid edges that they don't start off stuck in them
+    let mut extents = map.size * HALF;
+    if map.is_solid() {
+        extents -= Vec2::splat(
+            MATERIAL_SCALE * ASTEROID_DIMENSION_MULTIPLIERS[ASTEROID_SIZE_MAX as usize] * HALF,
+        );
+    }
+
+    for _ in 0..wave_size {
+        // along a random edge, so they don't land on top of anybody
+        let translation = if rng.gen::<bool>() {
+            Vec2::new(
+                extents.x * if rng.gen::<bool>() { 1.0 } else { -1.0 },
+                (rng.gen::<f32>() * 2.0 - 1.0) * extents.y,
+            )
+        } else {
+            Vec2::new(
+                (rng.gen::<f32>() * 2.0 - 1.0) * extents.x,
+                extents.y * if rng.gen::<bool>() { 1.0 } else { -1.0 },
+            )
+        };
+
+        let rotation = Quat::from_rotation_z(f32::to_radians(DEGREES_MAX * rng.gen::<f32>()));
+
+        let transform =
+            Transform::from_translation(translation.extend(0.0)).with_rotation(rotation);
+
+        let velocity = Velocity {
+            linvel: rotation
+                .mul_vec3(Vec3::new(0.0, ASTEROID_LINEAR_VELOCITY, 0.0))
+                .truncate(),
+            angvel: get_random_angvel(),
+        };
+
+        spawn_event_writer.send(get_spawn_event_for_asteroid(
+            ASTEROID_SIZE_MAX,
+            transform,
+            velocity,
+        ));
+    }
+
+    asteroid_waves.wave_number += 1;
+    asteroid_waves.last_cleared_at = time.elapsed_seconds_f64();
+}
+
+fn split_asteroid(
+    asteroid: &Asteroid,
+    collider: &Collider,
+    spawn_event_writer: &mut EventWriter<SpawnEvent>,
+) {
+    if asteroid.size <= 1 || collider.transform.is_none() {
+        return;
+    }
+
+    let size = asteroid.size - 1;
+    let transform = collider.transform.clone().unwrap().to_transform();
+
+    let mut linvel = Vec2::ZERO;
+    if collider.velocity.is_some() {
+        linvel = collider.velocity.clone().unwrap().linvel;
+    }
+
+    if linvel.length() < ASTEROID_LINEAR_VELOCITY {
+        linvel = transform
+            .rotation
+            .mul_vec3(Vec3::new(0.0, ASTEROID_LINEAR_VELOCITY, 0.0))
+            .truncate();
+    }
+
+    for i in 0..ASTEROID_SPLIT_COUNT {
+        // fan the pieces out either side of the direction of travel
+        let spread = (i as f32 / (ASTEROID_SPLIT_COUNT - 1).max(1) as f32) * 2.0 - 1.0;
+        let rotation =
+            Quat::from_rotation_z(f32::to_radians(spread * ASTEROID_SPLIT_ANGLE_DEGREES));
+
+        let direction = rotation.mul_vec3(linvel.extend(0.0)).truncate();
+
+        let offset = direction.normalize_or_zero()
+            * (MATERIAL_SCALE * ASTEROID_DIMENSION_MULTIPLIERS[size as usize] * HALF);
+
+        let mut piece_transform = transform;
+        piece_transform.translation += offset.extend(0.0);
+
+        let velocity = Velocity {
+            linvel: direction * ASTEROID_SPLIT_VELOCITY_MULTIPLIER,
+            angvel: get_random_angvel(),
+        };
+
+        spawn_event_writer.send(get_spawn_event_for_asteroid(
+            size,
+            piece_transform,
+            velocity,
+        ));
+    }
+}
+
+// collisions on the server only ever come from rapier (clients can't send them), so the colliders'
+// types and uuids can be trusted
+pub fn handle_collision_event_for_asteroid(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut asteroid_query: Query<&mut Asteroid>,
+    projectile_query: Query<&Projectile>,
+    mut player_query: Query<(&mut Player, &Weaponized)>,
+    mut damageable_query: Query<&mut Damageable>,
+    mut effect_query: Query<&mut Effect>,
+    match_state: Res<State<MatchState>>,
+    mut spawn_event_writer: EventWriter<SpawnEvent>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+    mut kill_event_writer: EventWriter<KillEvent>,
+    time: Res<Time>,
+) {
+    // scores and damage only count while a round is on
+    let is_in_round = *match_state.get() == MatchState::InRound;
+
+    for collision_event in collision_event_reader.read() {
+        let colliders = [
+            (&collision_event.collider_a, &collision_event.collider_b),
+            (&collision_event.collider_b, &collision_event.collider_a),
+        ];
+
+        for (collider, other_collider) in colliders.iter() {
+            if collider.collision_type != Some(CollisionType::Asteroid)
+                || !(other_collider.collision_type == Some(CollisionType::Projectile)
+                    || other_collider.collision_type == Some(CollisionType::Player))
+            {
+                continue;
+            }
+
+            let asteroid = asteroid_query
+                .iter_mut()
+                .find(|asteroid| asteroid.asteroid_uuid == collider.entity_uuid);
+
+            if asteroid.is_none() {
+                continue;
+            }
+
+            let mut asteroid = asteroid.unwrap();
+            if asteroid.is_destroyed {
+                continue;
+            }
+
+            if other_collider.collision_type == Some(CollisionType::Projectile) {
+                let projectile = projectile_query
+                    .iter()
+                    .find(|projectile| projectile.projectile_uuid == other_collider.entity_uuid);
+
+                if is_in_round && projectile.is_some() {
+                    for (mut player, weaponized) in player_query.iter_mut() {
+                        if weaponized.weapon_uuid == projectile.unwrap().weapon_uuid {
+                            player.score += ASTEROID_SCORES[asteroid.size as usize];
+                        }
+                    }
+                }
+
+                despawn_event_writer.send(DespawnEvent {
+                    entity_uuid: other_collider.entity_uuid,
+                    entity_type: other_collider.entity_type.clone(),
+                });
+            } else if is_in_round {
+                for mut damageable in damageable_query.iter_mut() {
+                    if damageable.entity_uuid != other_collider.entity_uuid {
+                        continue;
+                    }
+
+                    let amount = absorb_damage(
+                        &mut effect_query,
+                        damageable.entity_uuid,
+                        ASTEROID_DAMAGES[asteroid.size as usize],
+                    );
+
+                    if !damageable.damage(amount) {
+                        continue;
+                    }
+
+                    kill_event_writer.send(KillEvent {
+                        killer_uuid: None,
+                        victim_uuid: damageable.entity_uuid,
+                        server_time: time.elapsed_seconds_f64(),
+                    });
+                }
+            }
+
+            split_asteroid(&asteroid, collider, &mut spawn_event_writer);
+
+            asteroid.is_destroyed = true;
+
+            despawn_event_writer.send(DespawnEvent {
+                entity_uuid: asteroid.asteroid_uuid,
+                entity_type: "asteroid".to_string(),
+            });
+        }
+    }
+}
+
+// clean slate for each round; runs on entering the countdown
+pub fn handle_round_reset_for_asteroid(
+    asteroid_query: Query<&Asteroid>,
+    mut asteroid_waves: ResMut<AsteroidWaves>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+    time: Res<Time>,
+) {
+    for asteroid in asteroid_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: asteroid.asteroid_uuid,
+            entity_type: "asteroid".to_string(),
+        });
+    }
+
+    asteroid_waves.wave_number = 0;
+    asteroid_waves.last_cleared_at = time.elapsed_seconds_f64();
+}
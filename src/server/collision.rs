@@ -11,13 +11,8 @@ pub fn handle_collision_event(
     for collision_event in collision_event_reader.read() {
         let message = serialize(Container {
             message_type: "collision".to_string(),
-            join: None,
-            spawn: None,
-            update: None,
-            input: None,
-            despawn: None,
-            leave: None,
             collision: Some(collision_event.clone()),
+            ..Default::default()
         });
 
         // tell everyone to about the collision
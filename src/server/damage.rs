@@ -0,0 +1,175 @@
+use bevy::prelude::{EventReader, EventWriter, Query, Res, Time, Transform};
+use bevy_rapier2d::dynamics::Velocity;
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{CollisionEvent, CollisionType};
+use crate::behaviour::damageable::Damageable;
+use crate::behaviour::moveable::Moveable;
+use crate::behaviour::weaponized::Weaponized;
+use crate::constants::PLAYER_KILL_SCORE;
+use crate::identity::effect::{absorb_damage, ApplyEffectEvent, Effect};
+use crate::identity::map::Map;
+use crate::identity::player::Player;
+use crate::identity::projectile::Projectile;
+use crate::identity::round::MatchRules;
+use crate::identity::team::is_teammate;
+use crate::identity::weapon::get_weapon;
+use crate::server::join::get_spawn_transform;
+use crate::types::event::{DespawnEvent, KillEvent};
+
+pub fn respawn_player(
+    map: &Map,
+    damageable: &mut Damageable,
+    moveable: &mut Moveable,
+    transform: &mut Transform,
+    velocity: &mut Velocity,
+) {
+    damageable.reset();
+
+    // respawn somewhere else; flagged as a rollover so clients snap rather than smooth
+    *transform = get_spawn_transform(map).with_scale(transform.scale);
+    *velocity = Velocity::zero();
+    moveable.had_rollover = true;
+}
+
+// hits are what rapier says they are, since clients can't send the server collisions
+pub fn handle_collision_event_for_damageable(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    projectile_query: Query<&Projectile>,
+    weaponized_query: Query<(&Player, &Weaponized)>,
+    mut damageable_query: Query<&mut Damageable>,
+    mut effect_query: Query<&mut Effect>,
+    mut apply_effect_event_writer: EventWriter<ApplyEffectEvent>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+    mut kill_event_writer: EventWriter<KillEvent>,
+    rules: Res<MatchRules>,
+    time: Res<Time>,
+) {
+    let get_team = |player_uuid: Uuid| {
+        weaponized_query
+            .iter()
+            .find(|(player, _)| player.player_uuid == player_uuid)
+            .and_then(|(player, _)| player.team)
+    };
+
+    for collision_event in collision_event_reader.read() {
+        let colliders = [
+            (&collision_event.collider_a, &collision_event.collider_b),
+            (&collision_event.collider_b, &collision_event.collider_a),
+        ];
+
+        for (collider, other_collider) in colliders.iter() {
+            if collider.collision_type != Some(CollisionType::Projectile)
+                || other_collider.collision_type != Some(CollisionType::Player)
+            {
+                continue;
+            }
+
+            let projectile = projectile_query
+                .iter()
+                .find(|projectile| projectile.projectile_uuid == collider.entity_uuid);
+
+            if projectile.is_none() {
+                continue;
+            }
+
+            let projectile = projectile.unwrap();
+
+            let mut killer_uuid = None;
+            for (player, weaponized) in weaponized_query.iter() {
+                if weaponized.weapon_uuid == projectile.weapon_uuid {
+                    killer_uuid = Some(player.player_uuid);
+                }
+            }
+
+            let is_friendly = killer_uuid.is_some()
+                && is_teammate(
+                    get_team(killer_uuid.unwrap()),
+                    get_team(other_collider.entity_uuid),
+                );
+
+            for mut damageable in damageable_query.iter_mut() {
+                if damageable.entity_uuid != other_collider.entity_uuid
+                    || (is_friendly && !rules.is_friendly_fire)
+                {
+                    continue;
+                }
+
+                let weapon = get_weapon(&projectile.weapon_type);
+
+                if weapon.effect_on_hit.is_some() {
+                    apply_effect_event_writer.send(ApplyEffectEvent {
+                        player_uuid: damageable.entity_uuid,
+                        effect_type: weapon.effect_on_hit.unwrap().to_string(),
+                    });
+                }
+
+                let amount = absorb_damage(
+                    &mut effect_query,
+                    damageable.entity_uuid,
+                    weapon.get_damage(projectile.charge),
+                );
+
+                if !damageable.damage(amount) {
+                    continue;
+                }
+
+                kill_event_writer.send(KillEvent {
+                    killer_uuid,
+                    victim_uuid: damageable.entity_uuid,
+                    server_time: time.elapsed_seconds_f64(),
+                });
+            }
+
+            // projectiles are used up by whatever they hit
+            despawn_event_writer.send(DespawnEvent {
+                entity_uuid: collider.entity_uuid,
+                entity_type: collider.entity_type.clone(),
+            });
+        }
+    }
+}
+
+pub fn handle_kill_event_for_player(
+    mut kill_event_reader: EventReader<KillEvent>,
+    map: Res<Map>,
+    mut player_query: Query<(
+        &mut Player,
+        &mut Damageable,
+        &mut Moveable,
+        &mut Transform,
+        &mut Velocity,
+    )>,
+) {
+    for kill_event in kill_event_reader.read() {
+        let victim_team = player_query
+            .iter()
+            .find(|(player, _, _, _, _)| player.player_uuid == kill_event.victim_uuid)
+            .and_then(|(player, _, _, _, _)| player.team);
+
+        for (mut player, mut damageable, mut moveable, mut transform, mut velocity) in
+            player_query.iter_mut()
+        {
+            // no points for killing yourself or a teammate
+            if kill_event.killer_uuid.is_some()
+                && kill_event.killer_uuid.unwrap() == player.player_uuid
+                && kill_event.killer_uuid.unwrap() != kill_event.victim_uuid
+                && !is_teammate(player.team, victim_team)
+            {
+                player.score += PLAYER_KILL_SCORE;
+            }
+
+            if player.player_uuid != kill_event.victim_uuid {
+                continue;
+            }
+
+            respawn_player(
+                &map,
+                &mut damageable,
+                &mut moveable,
+                &mut transform,
+                &mut velocity,
+            );
+        }
+    }
+}
@@ -9,19 +9,10 @@ pub fn handle_despawn_event(
     mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
 ) {
     for despawn_event in despawn_event_reader.read() {
-        if despawn_event.entity_type == "particle" {
-            continue;
-        }
-
         let message = serialize(Container {
             message_type: "despawn".to_string(),
-            join: None,
-            spawn: None,
-            input: None,
-            update: None,
             despawn: Some(despawn_event.clone()),
-            leave: None,
-            collision: None,
+            ..Default::default()
         });
 
         // tell everyone to despawn the entity
@@ -0,0 +1,129 @@
+use bevy::prelude::{DetectChanges, EventReader, EventWriter, Query, Ref, Res, Time};
+use uuid::Uuid;
+
+use crate::base::helpers::serialize;
+use crate::behaviour::expireable::Expireable;
+use crate::identity::effect::{get_effect_rule, ApplyEffectEvent, Effect, Stacking};
+use crate::types::event::{DespawnEvent, EffectEvent, SpawnEvent};
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+pub fn handle_apply_effect_event(
+    mut apply_effect_event_reader: EventReader<ApplyEffectEvent>,
+    effect_query: Query<&Effect>,
+    mut spawn_event_writer: EventWriter<SpawnEvent>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+) {
+    // the spawns and despawns below don't land until later, so effects applied earlier in the same
+    // frame have to be counted here
+    let mut applied: Vec<(Uuid, &str)> = vec![];
+
+    for apply_effect_event in apply_effect_event_reader.read() {
+        let effect_rule = get_effect_rule(&apply_effect_event.effect_type);
+        if effect_rule.is_none() {
+            continue;
+        }
+
+        let effect_rule = effect_rule.unwrap();
+
+        let applied_count = applied
+            .iter()
+            .filter(|(player_uuid, effect_type)| {
+                *player_uuid == apply_effect_event.player_uuid
+                    && *effect_type == effect_rule.effect_type
+            })
+            .count();
+
+        // already refreshed (or stacked all the way) this frame
+        if (effect_rule.stacking == Stacking::Refresh && applied_count > 0)
+            || applied_count >= effect_rule.stacks_max
+        {
+            continue;
+        }
+
+        let mut existing_effects: Vec<&Effect> = effect_query
+            .iter()
+            .filter(|effect| {
+                effect.player_uuid == apply_effect_event.player_uuid
+                    && effect.effect_type == effect_rule.effect_type
+            })
+            .collect();
+
+        existing_effects.sort_by(|a, b| a.applied_at.total_cmp(&b.applied_at));
+
+        // replacing rather than updating in place means clients only ever see spawns and despawns
+        let mut replaced_count = 0;
+        let mut replace_count = existing_effects.len();
+        if effect_rule.stacking == Stacking::Stack {
+            replaced_count =
+                (existing_effects.len() + applied_count).saturating_sub(effect_rule.stacks_max);
+            replace_count =
+                (existing_effects.len() + applied_count + 1).saturating_sub(effect_rule.stacks_max);
+        }
+
+        for effect in existing_effects
+            .iter()
+            .take(replace_count)
+            .skip(replaced_count)
+        {
+            despawn_event_writer.send(DespawnEvent {
+                entity_uuid: effect.effect_uuid,
+                entity_type: "effect".to_string(),
+            });
+        }
+
+        spawn_event_writer.send(SpawnEvent {
+            entity_uuid: Uuid::new_v4(),
+            entity_type: "effect".to_string(),
+            pickup_type: Some(effect_rule.effect_type.to_string()),
+            player_uuid: Some(apply_effect_event.player_uuid),
+            ..Default::default()
+        });
+
+        applied.push((apply_effect_event.player_uuid, effect_rule.effect_type));
+    }
+}
+
+// shields that have soaked up all they can expire early
+pub fn handle_effect(
+    time: Res<Time>,
+    mut effect_query: Query<(Ref<Effect>, &mut Expireable)>,
+    mut effect_event_writer: EventWriter<EffectEvent>,
+) {
+    for (effect, mut expireable) in effect_query.iter_mut() {
+        if effect.effect_type != "shield" {
+            continue;
+        }
+
+        // so the client can show what's left
+        if effect.is_changed() && !effect.is_added() {
+            effect_event_writer.send(EffectEvent {
+                effect_uuid: effect.effect_uuid,
+                magnitude: effect.magnitude,
+            });
+        }
+
+        if effect.magnitude > 0.0 {
+            continue;
+        }
+
+        expireable.expires_at = expireable.expires_at.min(time.elapsed_seconds_f64());
+    }
+}
+
+pub fn handle_effect_event(
+    mut effect_event_reader: EventReader<EffectEvent>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for effect_event in effect_event_reader.read() {
+        // everybody can see everybody's shields
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: None,
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "effect".to_string(),
+                effect: Some(effect_event.clone()),
+                ..Default::default()
+            }),
+        });
+    }
+}
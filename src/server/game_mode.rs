@@ -0,0 +1,397 @@
+use std::f32::consts::PI;
+
+use bevy::math::Vec2;
+use bevy::prelude::{EventReader, EventWriter, Query, Res, ResMut, Time, Transform, Without};
+use uuid::Uuid;
+
+use crate::base::helpers::serialize;
+use crate::behaviour::collideable::{CollisionEvent, CollisionKind, CollisionType};
+use crate::constants::{
+    BASE_DISTANCE_MULTIPLIER, BASE_RADIUS, FLAG_CAPTURE_SCORE, FLAG_RETURN_SECONDS,
+    FLAG_TOUCH_DISTANCE, GAME_MODE_NETWORK_UPDATE_RATE_SECONDS, HALF,
+};
+use crate::identity::base::Base;
+use crate::identity::flag::Flag;
+use crate::identity::game_mode::{get_game_mode, Objectives};
+use crate::identity::map::Map;
+use crate::identity::player::Player;
+use crate::identity::round::MatchRules;
+use crate::identity::zone::Zone;
+use crate::types::event::{
+    DespawnEvent, FlagState, GameModeEvent, KillEvent, SerializableTransform, SpawnEvent,
+};
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+// spread around the middle of the arena, starting on the left
+fn get_base_translation(map: &Map, team: u32, team_count: u32) -> Vec2 {
+    let angle = PI + (2.0 * PI * team as f32) / team_count as f32;
+
+    Vec2::new(
+        angle.cos() * map.size.x * HALF * BASE_DISTANCE_MULTIPLIER,
+        angle.sin() * map.size.y * HALF * BASE_DISTANCE_MULTIPLIER,
+    )
+}
+
+fn get_spawn_event_for_objective(
+    entity_uuid: Uuid,
+    entity_type: &str,
+    translation: Vec2,
+    team: Option<u32>,
+) -> SpawnEvent {
+    SpawnEvent {
+        entity_uuid,
+        entity_type: entity_type.to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(translation.extend(0.0)),
+        )),
+        team,
+        ..Default::default()
+    }
+}
+
+// run condition for the handlers that only apply to one game mode
+pub fn is_game_mode(game_mode: &'static str) -> impl Fn(Res<MatchRules>) -> bool {
+    move |rules: Res<MatchRules>| rules.game_mode == game_mode
+}
+
+// clean slate for each round; runs on entering the countdown, before each mode sets itself up
+pub fn handle_round_reset_for_game_mode(
+    rules: Res<MatchRules>,
+    mut objectives: ResMut<Objectives>,
+    flag_query: Query<&Flag>,
+    base_query: Query<&Base>,
+    zone_query: Query<&Zone>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+) {
+    for flag in flag_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: flag.flag_uuid,
+            entity_type: "flag".to_string(),
+        });
+    }
+
+    for base in base_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: base.base_uuid,
+            entity_type: "base".to_string(),
+        });
+    }
+
+    for zone in zone_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: zone.zone_uuid,
+            entity_type: "zone".to_string(),
+        });
+    }
+
+    objectives.reset(rules.team_count);
+}
+
+// a base and a flag for each team
+pub fn handle_round_reset_for_ctf(
+    rules: Res<MatchRules>,
+    map: Res<Map>,
+    mut spawn_event_writer: EventWriter<SpawnEvent>,
+) {
+    for team in 0..rules.team_count {
+        let translation = get_base_translation(&map, team, rules.team_count);
+
+        spawn_event_writer.send(get_spawn_event_for_objective(
+            Uuid::new_v4(),
+            "base",
+            translation,
+            Some(team),
+        ));
+
+        spawn_event_writer.send(get_spawn_event_for_objective(
+            Uuid::new_v4(),
+            "flag",
+            translation,
+            Some(team),
+        ));
+    }
+}
+
+// the one zone, in the middle
+pub fn handle_round_reset_for_koth(mut spawn_event_writer: EventWriter<SpawnEvent>) {
+    spawn_event_writer.send(get_spawn_event_for_objective(
+        Uuid::new_v4(),
+        "zone",
+        Vec2::ZERO,
+        None,
+    ));
+}
+
+pub fn handle_flag(
+    time: Res<Time>,
+    mut objectives: ResMut<Objectives>,
+    mut flag_query: Query<(&mut Flag, &mut Transform)>,
+    mut player_query: Query<(&mut Player, &Transform), Without<Flag>>,
+    base_query: Query<(&Base, &Transform), (Without<Flag>, Without<Player>)>,
+) {
+    let now = time.elapsed_seconds_f64();
+
+    // carried flags follow their carrier; dropped flags eventually go home on their own
+    for (mut flag, mut transform) in flag_query.iter_mut() {
+        if flag.carrier_uuid.is_some() {
+            let carrier = player_query
+                .iter()
+                .find(|(player, _)| player.player_uuid == flag.carrier_uuid.unwrap());
+
+            // the carrier left
+            if carrier.is_none() {
+                flag.drop(now);
+                continue;
+            }
+
+            let (_, carrier_transform) = carrier.unwrap();
+            transform.translation = carrier_transform
+                .translation
+                .truncate()
+                .extend(transform.translation.z);
+
+            continue;
+        }
+
+        if flag.dropped_at.is_some() && now - flag.dropped_at.unwrap() >= FLAG_RETURN_SECONDS {
+            flag.return_home();
+        }
+
+        if flag.is_at_home() {
+            transform.translation = flag.home.extend(transform.translation.z);
+        }
+    }
+
+    // touching an enemy flag picks it up, touching your own dropped flag sends it home
+    let mut carrier_uuids: Vec<Uuid> = flag_query
+        .iter()
+        .filter_map(|(flag, _)| flag.carrier_uuid)
+        .collect();
+
+    for (mut flag, mut transform) in flag_query.iter_mut() {
+        if flag.carrier_uuid.is_some() {
+            continue;
+        }
+
+        for (player, player_transform) in player_query.iter() {
+            if player.team.is_none()
+                || player_transform
+                    .translation
+                    .truncate()
+                    .distance(transform.translation.truncate())
+                    > FLAG_TOUCH_DISTANCE
+            {
+                continue;
+            }
+
+            if player.team.unwrap() == flag.team {
+                if !flag.is_at_home() {
+                    flag.return_home();
+                    transform.translation = flag.home.extend(transform.translation.z);
+                }
+
+                continue;
+            }
+
+            // one flag at a time
+            if carrier_uuids.contains(&player.player_uuid) {
+                continue;
+            }
+
+            flag.carrier_uuid = Some(player.player_uuid);
+            flag.dropped_at = None;
+            carrier_uuids.push(player.player_uuid);
+
+            break;
+        }
+    }
+
+    // a capture needs your own flag to be at home
+    let teams_at_home: Vec<u32> = flag_query
+        .iter()
+        .filter(|(flag, _)| flag.is_at_home())
+        .map(|(flag, _)| flag.team)
+        .collect();
+
+    for (mut flag, mut transform) in flag_query.iter_mut() {
+        if flag.carrier_uuid.is_none() {
+            continue;
+        }
+
+        let carrier = player_query
+            .iter_mut()
+            .find(|(player, _)| player.player_uuid == flag.carrier_uuid.unwrap());
+
+        if carrier.is_none() {
+            continue;
+        }
+
+        let (mut player, player_transform) = carrier.unwrap();
+        let team = player.team.unwrap_or_default();
+
+        if !teams_at_home.contains(&team) {
+            continue;
+        }
+
+        let is_at_base = base_query.iter().any(|(base, base_transform)| {
+            base.team == team
+                && base_transform
+                    .translation
+                    .truncate()
+                    .distance(player_transform.translation.truncate())
+                    <= BASE_RADIUS
+        });
+
+        if !is_at_base {
+            continue;
+        }
+
+        player.score += FLAG_CAPTURE_SCORE;
+        objectives.add(team, 1.0);
+
+        flag.return_home();
+        transform.translation = flag.home.extend(transform.translation.z);
+    }
+}
+
+pub fn handle_kill_event_for_flag(
+    mut kill_event_reader: EventReader<KillEvent>,
+    time: Res<Time>,
+    mut flag_query: Query<&mut Flag>,
+) {
+    for kill_event in kill_event_reader.read() {
+        for mut flag in flag_query.iter_mut() {
+            // left where the carrier died
+            if flag.carrier_uuid.is_some() && flag.carrier_uuid.unwrap() == kill_event.victim_uuid {
+                flag.drop(time.elapsed_seconds_f64());
+            }
+        }
+    }
+}
+
+// keeps track of who's in the zone as they come and go
+pub fn handle_collision_event_for_zone(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut zone_query: Query<&mut Zone>,
+) {
+    for collision_event in collision_event_reader.read() {
+        let colliders = [
+            (&collision_event.collider_a, &collision_event.collider_b),
+            (&collision_event.collider_b, &collision_event.collider_a),
+        ];
+
+        for (collider, other_collider) in colliders.iter() {
+            if collider.collision_type != Some(CollisionType::Zone)
+                || other_collider.collision_type != Some(CollisionType::Player)
+            {
+                continue;
+            }
+
+            for mut zone in zone_query.iter_mut() {
+                if zone.zone_uuid != collider.entity_uuid {
+                    continue;
+                }
+
+                let player_uuid = other_collider.entity_uuid;
+
+                if collision_event.kind == CollisionKind::Separation {
+                    zone.occupant_uuids
+                        .retain(|occupant_uuid| *occupant_uuid != player_uuid);
+                } else if !zone.occupant_uuids.contains(&player_uuid) {
+                    zone.occupant_uuids.push(player_uuid);
+                }
+            }
+        }
+    }
+}
+
+pub fn handle_zone(
+    time: Res<Time>,
+    mut objectives: ResMut<Objectives>,
+    zone_query: Query<&Zone>,
+    player_query: Query<&Player>,
+) {
+    objectives.zone_team = None;
+
+    for zone in zone_query.iter() {
+        let mut teams: Vec<u32> = vec![];
+
+        // anybody that left the game while in the zone never comes back out of it
+        for player in player_query.iter() {
+            if player.team.is_none() || !zone.occupant_uuids.contains(&player.player_uuid) {
+                continue;
+            }
+
+            if !teams.contains(&player.team.unwrap()) {
+                teams.push(player.team.unwrap());
+            }
+        }
+
+        // contested zones don't count for anybody
+        if teams.len() != 1 {
+            continue;
+        }
+
+        objectives.zone_team = Some(teams[0]);
+        objectives.add(teams[0], time.delta_seconds());
+    }
+}
+
+pub fn handle_objectives(
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    mut objectives: ResMut<Objectives>,
+    flag_query: Query<(&Flag, &Transform)>,
+    mut game_mode_event_writer: EventWriter<GameModeEvent>,
+) {
+    let game_mode = get_game_mode(&rules.game_mode);
+    if game_mode.objective_name.is_none() {
+        return;
+    }
+
+    if time.elapsed_seconds_f64() - objectives.last_update_at
+        < GAME_MODE_NETWORK_UPDATE_RATE_SECONDS
+    {
+        return;
+    }
+
+    let flags = flag_query
+        .iter()
+        .map(|(flag, transform)| FlagState {
+            flag_uuid: flag.flag_uuid,
+            team: flag.team,
+            carrier_uuid: flag.carrier_uuid,
+            translation: transform.translation.truncate(),
+        })
+        .collect();
+
+    objectives.last_update_at = time.elapsed_seconds_f64();
+
+    game_mode_event_writer.send(GameModeEvent {
+        server_time: time.elapsed_seconds_f64(),
+        game_mode: game_mode.game_mode.to_string(),
+        objective_name: game_mode.objective_name.map(|name| name.to_string()),
+        objective_limit: game_mode.objective_limit,
+        objectives: objectives.scores.clone(),
+        flags,
+        zone_team: objectives.zone_team,
+    });
+}
+
+pub fn handle_game_mode_event(
+    mut game_mode_event_reader: EventReader<GameModeEvent>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for game_mode_event in game_mode_event_reader.read() {
+        // tell everyone how the objectives are going
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: None,
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "game_mode".to_string(),
+                game_mode: Some(game_mode_event.clone()),
+                ..Default::default()
+            }),
+        });
+    }
+}
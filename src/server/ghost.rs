@@ -0,0 +1,51 @@
+use bevy::prelude::{Commands, Entity, Query, Res, Transform, TransformBundle, With, Without};
+use bevy_rapier2d::dynamics::RigidBody::KinematicPositionBased;
+use bevy_rapier2d::geometry::{
+    ActiveCollisionTypes, ActiveEvents, Collider as RapierCollider, CollisionGroups, Sensor,
+};
+
+use crate::behaviour::collideable::Collideable;
+use crate::behaviour::moveable::Moveable;
+use crate::behaviour::wrappable::{get_ghost_transform, get_wrap_offsets, update_ghosts, Ghost};
+use crate::identity::map::Map;
+
+// sensor copies of anything hanging over an edge, on the opposite side, so things can be hit
+// across the wrap; collisions with them are put down to the source
+pub fn handle_ghost_for_collideable(
+    mut commands: Commands,
+    map: Res<Map>,
+    source_query: Query<
+        (Entity, &Transform, &RapierCollider, &CollisionGroups),
+        (With<Moveable>, With<Collideable>, Without<Ghost>),
+    >,
+    mut ghost_query: Query<(Entity, &Ghost, &mut Transform), Without<Moveable>>,
+) {
+    let mut needed = vec![];
+
+    if !map.is_solid() {
+        for (entity, transform, _, _) in source_query.iter() {
+            for offset in get_wrap_offsets(transform, map.size) {
+                needed.push((entity, offset, get_ghost_transform(transform, offset)));
+            }
+        }
+    }
+
+    update_ghosts(&mut needed, ghost_query.iter_mut(), &mut commands);
+
+    for (source, offset, transform) in needed {
+        let (_, _, collider, collision_groups) = source_query.get(source).unwrap();
+
+        // not spawned through a SpawnEvent; ghosts are local to the server and never sent
+        commands.spawn((
+            TransformBundle::from_transform(transform),
+            collider.clone(),
+            *collision_groups,
+            Sensor,
+            KinematicPositionBased,
+            ActiveEvents::all(),
+            // so they still run into walls
+            ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_FIXED,
+            Ghost { source, offset },
+        ));
+    }
+}
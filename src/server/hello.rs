@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use bevy::prelude::{trace, Color, EventReader, EventWriter, Query, Res, Time};
+
+use crate::constants::{PLAYER_NAME_DEFAULT, PLAYER_NAME_LENGTH_MAX};
+use crate::identity::game::Game;
+use crate::identity::map::Map;
+use crate::identity::player::Player;
+use crate::types::event::{HelloEvent, JoinEvent};
+
+pub fn sanitize_name(name: &str, taken_names: &HashSet<String>) -> String {
+    let name: String = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .collect();
+
+    let mut name: String = name
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .chars()
+        .take(PLAYER_NAME_LENGTH_MAX)
+        .collect();
+
+    if name.is_empty() {
+        name = PLAYER_NAME_DEFAULT.to_string();
+    }
+
+    // names are unique regardless of case; clashes get a numeric suffix
+    let mut unique_name = name.clone();
+    let mut suffix = 2;
+
+    while taken_names.contains(&unique_name.to_lowercase()) {
+        let suffix_text = format!(" {:}", suffix);
+        let prefix: String = name
+            .chars()
+            .take(PLAYER_NAME_LENGTH_MAX - suffix_text.len())
+            .collect();
+
+        unique_name = format!("{:}{:}", prefix.trim_end(), suffix_text);
+        suffix += 1;
+    }
+
+    unique_name
+}
+
+// clamp lets NaN through untouched, so anything that isn't a number counts as none of that channel
+fn sanitize_channel(channel: f32) -> f32 {
+    if !channel.is_finite() {
+        return 0.0;
+    }
+
+    channel.clamp(0.0, 1.0)
+}
+
+pub fn sanitize_color(color: Color) -> Color {
+    let color = color.to_srgba();
+
+    Color::srgb(
+        sanitize_channel(color.red),
+        sanitize_channel(color.green),
+        sanitize_channel(color.blue),
+    )
+}
+
+pub fn handle_hello_event(
+    mut hello_event_reader: EventReader<HelloEvent>,
+    game: Res<Game>,
+    map: Res<Map>,
+    player_query: Query<&Player>,
+    mut join_event_writer: EventWriter<JoinEvent>,
+    time: Res<Time>,
+) {
+    let mut taken_names = HashSet::new();
+
+    for player in player_query.iter() {
+        taken_names.insert(player.name.to_lowercase());
+    }
+
+    for hello in hello_event_reader.read() {
+        // ignore repeated hellos from a session that has already joined
+        if game.player_uuids.contains(&hello.session_uuid) {
+            continue;
+        }
+
+        let name = sanitize_name(hello.name.as_str(), &taken_names);
+        taken_names.insert(name.to_lowercase());
+
+        trace!(
+            "handle_hello_event; session_uuid={:?}, name={:?}",
+            hello.session_uuid,
+            name
+        );
+
+        join_event_writer.send(JoinEvent {
+            player_uuid: hello.session_uuid,
+            is_for_local_player: true,
+            server_time: time.elapsed_seconds_f64(),
+            name,
+            color: sanitize_color(hello.color),
+            // picked when the join is handled
+            team: None,
+            is_wrapping: !map.is_solid(),
+            arena_size: map.size,
+        });
+    }
+}
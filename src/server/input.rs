@@ -5,6 +5,7 @@ use crate::behaviour::weaponized::{FireEvent, Weaponized};
 use crate::constants::{
     PLAYER_ANGULAR_VELOCITY_MAX, PLAYER_ANGULAR_VELOCITY_STEP, PLAYER_LINEAR_VELOCITY_MAX,
 };
+use crate::identity::effect::{get_effect_modifiers, Effect};
 use crate::identity::player::Player;
 use crate::types::event::InputEvent;
 
@@ -24,13 +25,20 @@ pub fn handle_input_event(
 }
 
 pub fn handle_input_for_player(
-    mut player_query: Query<(&mut Player, &Transform, &mut Velocity, &Weaponized)>,
+    mut player_query: Query<(&mut Player, &Transform, &mut Velocity, &mut Weaponized)>,
+    effect_query: Query<&Effect>,
     mut fire_event_writer: EventWriter<FireEvent>,
 ) {
-    for (mut player, transform, mut velocity, weaponized) in player_query.iter_mut() {
+    for (mut player, transform, mut velocity, mut weaponized) in player_query.iter_mut() {
+        let effect_modifiers = get_effect_modifiers(effect_query.iter(), player.player_uuid);
+        let speed_multiplier = effect_modifiers.speed_multiplier;
+
         let mut inputs = player.unhandled_inputs.clone();
         player.unhandled_inputs.clear();
 
+        // switching and releasing fire happen on the edge, not for as long as they're held
+        let mut previous_input = player.last_input.clone();
+
         if inputs.is_empty() {
             if player.last_input.is_some() {
                 inputs.insert(0, player.clone().last_input.unwrap());
@@ -40,31 +48,53 @@ pub fn handle_input_for_player(
         }
 
         for last_input in inputs.iter() {
-            if last_input.is_left && velocity.angvel <= PLAYER_ANGULAR_VELOCITY_MAX {
-                velocity.angvel += PLAYER_ANGULAR_VELOCITY_STEP;
+            // stunned players just drift
+            if effect_modifiers.is_stunned {
+                previous_input = Some(last_input.clone());
+                continue;
             }
 
-            if last_input.is_right && velocity.angvel >= -PLAYER_ANGULAR_VELOCITY_MAX {
-                velocity.angvel -= PLAYER_ANGULAR_VELOCITY_STEP;
+            // a partial turn is both a slower top rate and a slower build up to it (keys are
+            // always all the way)
+            let turn = last_input.get_turn();
+
+            if turn < 0.0 && velocity.angvel <= PLAYER_ANGULAR_VELOCITY_MAX * -turn {
+                velocity.angvel += PLAYER_ANGULAR_VELOCITY_STEP * -turn;
             }
 
-            if last_input.is_forward {
-                velocity.linvel += transform
-                    .rotation
-                    .mul_vec3(Vec3::new(0.0, PLAYER_LINEAR_VELOCITY_MAX, 0.0))
-                    .truncate();
+            if turn > 0.0 && velocity.angvel >= -PLAYER_ANGULAR_VELOCITY_MAX * turn {
+                velocity.angvel -= PLAYER_ANGULAR_VELOCITY_STEP * turn;
             }
 
-            if last_input.is_backward {
+            let thrust = last_input.get_thrust();
+
+            if thrust != 0.0 {
                 velocity.linvel += transform
                     .rotation
-                    .mul_vec3(Vec3::new(0.0, -PLAYER_LINEAR_VELOCITY_MAX, 0.0))
+                    .mul_vec3(Vec3::new(
+                        0.0,
+                        PLAYER_LINEAR_VELOCITY_MAX * speed_multiplier * thrust,
+                        0.0,
+                    ))
                     .truncate();
             }
 
-            if last_input.is_firing {
+            let was_firing =
+                previous_input.is_some() && previous_input.clone().unwrap().is_firing();
+            let was_switching_weapon =
+                previous_input.is_some() && previous_input.clone().unwrap().is_switching_weapon();
+
+            if last_input.is_switching_weapon() && !was_switching_weapon {
+                weaponized.switch_weapon();
+            }
+
+            if last_input.is_firing() {
                 weaponized.fire(&mut fire_event_writer);
+            } else if was_firing {
+                weaponized.release(&mut fire_event_writer);
             }
+
+            previous_input = Some(last_input.clone());
         }
     }
 }
@@ -1,21 +1,77 @@
+use bevy::ecs::world::EntityRef;
 use bevy::math::{Quat, Vec2};
-use bevy::prelude::{Color, EventReader, EventWriter, Query, Transform};
+use bevy::prelude::{EventReader, EventWriter, Query, Res, Transform};
 use bevy_rapier2d::dynamics::Velocity;
 use rand::{thread_rng, Rng};
 
 use crate::base::helpers::serialize;
-use crate::constants::{BOUNDS, DEGREES_MAX};
+use crate::behaviour::identifiable::Identifiable;
+use crate::constants::{DEGREES_MAX, MAP_CLEAR_ATTEMPTS, MATERIAL_SCALE};
+use crate::identity::entity::{encode_entity, EntityKinds};
+use crate::identity::map::Map;
 use crate::identity::player::Player;
+use crate::identity::round::MatchRules;
+use crate::identity::team::{get_balanced_team, get_team_color};
 use crate::types::event::{JoinEvent, SerializableTransform, SerializableVelocity, SpawnEvent};
 use crate::types::network::{Container, OutgoingMessageEvent};
 
+pub fn get_spawn_transform(map: &Map) -> Transform {
+    let mut rng = thread_rng();
+
+    let mut translation = Vec2::from((
+        rng.gen::<f32>() * map.size.x - (map.size.x / 2.0),
+        rng.gen::<f32>() * map.size.y - (map.size.y / 2.0),
+    ));
+
+    if !map.spawn_points.is_empty() {
+        translation = map.spawn_points[rng.gen_range(0..map.spawn_points.len())];
+    } else {
+        // keep out of the walls
+        for _ in 0..MAP_CLEAR_ATTEMPTS {
+            if map.is_clear(translation, MATERIAL_SCALE) {
+                break;
+            }
+
+            translation = Vec2::from((
+                rng.gen::<f32>() * map.size.x - (map.size.x / 2.0),
+                rng.gen::<f32>() * map.size.y - (map.size.y / 2.0),
+            ));
+        }
+    }
+
+    let translation = translation.extend(
+        rng.gen::<f32>() / 2.0, // all players between 0.0 and 0.5 as Z index
+    );
+
+    let rotation = Quat::from_rotation_z(f32::to_radians(DEGREES_MAX * rng.gen::<f32>()));
+
+    // TODO: something to avoid spawn position collision
+    Transform::from_translation(translation).with_rotation(rotation)
+}
+
 pub fn handle_join_event(
     mut join_event_reader: EventReader<JoinEvent>,
     mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
-    player_query: Query<(&Player, &Transform, &Velocity)>,
+    rules: Res<MatchRules>,
+    map: Res<Map>,
+    player_query: Query<&Player>,
+    identifiable_query: Query<(&Identifiable, EntityRef)>,
+    entity_kinds: Res<EntityKinds>,
     mut spawn_event_writer: EventWriter<SpawnEvent>,
 ) {
+    let mut teams: Vec<Option<u32>> = player_query.iter().map(|player| player.team).collect();
+
     for join_event in join_event_reader.read() {
+        let mut join_event = join_event.clone();
+
+        // auto-balance; the team colour wins over whatever the joiner picked
+        join_event.team = get_balanced_team(rules.team_count, &teams);
+        if join_event.team.is_some() {
+            join_event.color = get_team_color(join_event.team.unwrap());
+        }
+
+        teams.push(join_event.team);
+
         // tell the joiner about itself
         outgoing_message_event_writer.send(OutgoingMessageEvent {
             session_uuid: Some(join_event.player_uuid),
@@ -23,12 +79,7 @@ pub fn handle_join_event(
             message: serialize(Container {
                 message_type: "join".to_string(),
                 join: Some(join_event.clone()),
-                spawn: None,
-                input: None,
-                update: None,
-                despawn: None,
-                leave: None,
-                collision: None,
+                ..Default::default()
             }),
         });
 
@@ -41,34 +92,13 @@ pub fn handle_join_event(
             message: serialize(Container {
                 message_type: "join".to_string(),
                 join: Some(join_event_for_everyone_else.clone()),
-                spawn: None,
-                input: None,
-                update: None,
-                despawn: None,
-                leave: None,
-                collision: None,
+                ..Default::default()
             }),
         });
 
-        let mut rng = thread_rng();
-
-        let translation = Vec2::from((
-            rng.gen::<f32>() * BOUNDS.x - (BOUNDS.x / 2.0),
-            rng.gen::<f32>() * BOUNDS.y - (BOUNDS.y / 2.0),
-        ));
-
-        let translation = translation.extend(
-            rng.gen::<f32>() / 2.0, // all players between 0.0 and 0.5 as Z index
-        );
-
-        let rotation =
-            Quat::from_rotation_z(f32::to_radians(DEGREES_MAX * thread_rng().gen::<f32>()));
-
-        // TODO: something to avoid spawn position collision
-        let transform = Transform::from_translation(translation).with_rotation(rotation);
+        let transform = get_spawn_transform(&map);
 
         let velocity = Velocity::zero();
-        let color = Color::srgb(rng.gen::<f32>(), rng.gen::<f32>(), rng.gen::<f32>());
 
         // tell everyone else to spawn the joiner
         spawn_event_writer.send(SpawnEvent {
@@ -76,18 +106,19 @@ pub fn handle_join_event(
             entity_type: "player".to_string(),
             transform: Some(SerializableTransform::from_transform(transform)),
             velocity: Some(SerializableVelocity::from_velocity(velocity)),
-            color: Some(color),
+            color: Some(join_event.color),
+            name: Some(join_event.name.clone()),
+            team: join_event.team,
+            ..Default::default()
         });
 
-        // tell everyone to ensure everyone is spawned
-        for (player, transform, velocity) in player_query.iter() {
-            spawn_event_writer.send(SpawnEvent {
-                entity_uuid: player.player_uuid,
-                entity_type: "player".to_string(),
-                transform: Some(SerializableTransform::from_transform(*transform)),
-                velocity: Some(SerializableVelocity::from_velocity(*velocity)),
-                color: Some(player.color),
-            });
+        // tell everyone to ensure everything that's around is spawned
+        for (identifiable, entity_ref) in identifiable_query.iter() {
+            let spawn_event = encode_entity(&entity_kinds, identifiable, &entity_ref);
+
+            if spawn_event.is_some() {
+                spawn_event_writer.send(spawn_event.unwrap());
+            }
         }
     }
 }
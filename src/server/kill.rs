@@ -0,0 +1,23 @@
+use bevy::prelude::{EventReader, EventWriter};
+
+use crate::base::helpers::serialize;
+use crate::types::event::KillEvent;
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+pub fn handle_kill_event(
+    mut kill_event_reader: EventReader<KillEvent>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for kill_event in kill_event_reader.read() {
+        // tell everyone about the kill
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: None,
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "kill".to_string(),
+                kill: Some(kill_event.clone()),
+                ..Default::default()
+            }),
+        });
+    }
+}
@@ -21,13 +21,8 @@ pub fn handle_leave_event(
             not_session_uuid: None,
             message: serialize(Container {
                 message_type: "leave".to_string(),
-                join: None,
-                spawn: None,
-                input: None,
-                update: None,
-                despawn: None,
                 leave: Some(leave_event.clone()),
-                collision: None,
+                ..Default::default()
             }),
         });
     }
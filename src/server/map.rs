@@ -0,0 +1,127 @@
+use std::env;
+use std::fs;
+
+use bevy::log::warn;
+use bevy::math::Vec2;
+use bevy::prelude::{EventReader, EventWriter, Res, Transform};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{CollisionEvent, CollisionType};
+use crate::constants::BOUNDS;
+use crate::identity::map::Map;
+use crate::types::event::{DespawnEvent, SerializableTransform, SpawnEvent};
+
+fn load_map(path: &str) -> Map {
+    if path.is_empty() {
+        return Map::default();
+    }
+
+    let contents = fs::read_to_string(path);
+    if contents.is_err() {
+        warn!(
+            "load_map; couldn't read path={:?}: {:?}",
+            path,
+            contents.err()
+        );
+        return Map::default();
+    }
+
+    let map = serde_json::from_str::<Map>(contents.unwrap().as_str());
+    if map.is_err() {
+        warn!("load_map; couldn't parse path={:?}: {:?}", path, map.err());
+        return Map::default();
+    }
+
+    let mut map = map.unwrap();
+
+    // everything from the minimap to the camera limits divides by it
+    if map.size.x <= 0.0 || map.size.y <= 0.0 {
+        warn!(
+            "load_map; ignoring size={:?} for path={:?}, using BOUNDS",
+            map.size, path
+        );
+        map.size = BOUNDS;
+    }
+
+    map
+}
+
+// the map file comes from the MAP env var (no map, or a broken one, is just an empty arena) and
+// the ARENA_BOUNDARY env var wins over whatever edges it has
+pub fn get_map() -> Map {
+    let mut map = load_map(env::var("MAP").unwrap_or_default().as_str());
+
+    let boundary = env::var("ARENA_BOUNDARY").unwrap_or_default();
+    if boundary == "wrap" || boundary == "solid" {
+        map.edges = boundary;
+    }
+
+    map
+}
+
+// walls are positioned at their middle, so anything reported about them (e.g. collisions) is too;
+// entity_type is "wall" or "boundary" (for the solid edges)
+fn get_spawn_event_for_wall(
+    wall_uuid: Uuid,
+    entity_type: &str,
+    translation: Vec2,
+    points: Vec<Vec2>,
+) -> SpawnEvent {
+    SpawnEvent {
+        entity_uuid: wall_uuid,
+        entity_type: entity_type.to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(translation.extend(0.0)),
+        )),
+        points: Some(points),
+        ..Default::default()
+    }
+}
+
+pub fn handle_map_setup(map: Res<Map>, mut spawn_event_writer: EventWriter<SpawnEvent>) {
+    let edge_walls = map.get_edge_walls();
+
+    let walls = map.walls.iter().map(|outline| ("wall", outline));
+    let boundaries = edge_walls.iter().map(|outline| ("boundary", outline));
+
+    for (entity_type, outline) in walls.chain(boundaries) {
+        if outline.is_empty() {
+            continue;
+        }
+
+        let middle = outline.iter().copied().sum::<Vec2>() / outline.len() as f32;
+
+        spawn_event_writer.send(get_spawn_event_for_wall(
+            Uuid::new_v4(),
+            entity_type,
+            middle,
+            outline.iter().map(|point| *point - middle).collect(),
+        ));
+    }
+}
+
+pub fn handle_collision_event_for_wall(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+) {
+    for collision_event in collision_event_reader.read() {
+        let colliders = [
+            (&collision_event.collider_a, &collision_event.collider_b),
+            (&collision_event.collider_b, &collision_event.collider_a),
+        ];
+
+        for (collider, other_collider) in colliders.iter() {
+            if collider.collision_type != Some(CollisionType::Wall)
+                || other_collider.collision_type != Some(CollisionType::Projectile)
+            {
+                continue;
+            }
+
+            // projectiles are used up by walls (but bounce off the boundary)
+            despawn_event_writer.send(DespawnEvent {
+                entity_uuid: other_collider.entity_uuid,
+                entity_type: other_collider.entity_type.clone(),
+            });
+        }
+    }
+}
@@ -1,12 +1,26 @@
 pub mod app;
+pub mod asteroid;
 pub mod collision;
+pub mod damage;
 pub mod despawn;
+pub mod effect;
+pub mod game_mode;
+pub mod ghost;
+pub mod hello;
 pub mod input;
 pub mod join;
+pub mod kill;
 pub mod leave;
+pub mod map;
 pub mod moveable;
 pub mod network;
+pub mod pickup;
+pub mod ping;
+pub mod projectile;
+pub mod round;
+pub mod scoreboard;
 pub mod setup;
 pub mod spawn;
 pub mod update;
+pub mod weapon;
 pub mod websocket;
@@ -2,14 +2,15 @@ use bevy::prelude::{EventWriter, Query, Res, Time, Transform};
 use bevy_rapier2d::dynamics::Velocity;
 
 use crate::behaviour::moveable::Moveable;
+use crate::identity::player::Player;
 use crate::types::event::{SerializableTransform, SerializableVelocity, UpdateEvent};
 
 pub fn handle_update_for_moveable(
     time: Res<Time>,
-    mut moveable_query: Query<(&mut Moveable, &Transform, &Velocity)>,
+    mut moveable_query: Query<(&mut Moveable, &Transform, &Velocity, Option<&Player>)>,
     mut update_event_writer: EventWriter<UpdateEvent>,
 ) {
-    for (mut moveable, transform, velocity) in moveable_query.iter_mut() {
+    for (mut moveable, transform, velocity, player) in moveable_query.iter_mut() {
         if time.elapsed_seconds_f64() - moveable.last_update_handled_at
             < moveable.update_rate_seconds
         {
@@ -23,6 +24,7 @@ pub fn handle_update_for_moveable(
             velocity: Some(SerializableVelocity::from_velocity(*velocity)),
             handled_at: None,
             includes_rollover: moveable.had_rollover,
+            input: player.and_then(|player| player.last_input.clone()),
         };
 
         update_event_writer.send(update);
@@ -2,11 +2,10 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use bevy::log::trace;
-use bevy::prelude::{EventReader, EventWriter, NonSend, Query, Res, Time};
+use bevy::prelude::{EventReader, EventWriter, NonSend};
 
-use crate::identity::player::Player;
 use crate::server::websocket::WebSocketServer;
-use crate::types::event::{JoinEvent, LeaveEvent};
+use crate::types::event::LeaveEvent;
 use crate::types::network::{CloseEvent, IncomingMessageEvent, OpenEvent, OutgoingMessageEvent};
 
 pub fn handle_websocket_server(
@@ -95,27 +94,6 @@ pub fn handle_websocket_server(
     }
 }
 
-pub fn handle_open_event(
-    mut open_event_reader: EventReader<OpenEvent>,
-    player_query: Query<&Player>,
-    mut join_event_writer: EventWriter<JoinEvent>,
-    time: Res<Time>,
-) {
-    for open_event in open_event_reader.read() {
-        let mut other_player_uuids = vec![];
-
-        for other_player in player_query.iter() {
-            other_player_uuids.push(other_player.player_uuid);
-        }
-
-        join_event_writer.send(JoinEvent {
-            player_uuid: open_event.session_uuid,
-            is_for_local_player: true,
-            server_time: time.elapsed_seconds_f64(),
-        });
-    }
-}
-
 pub fn handle_close_event(
     mut close_event_reader: EventReader<CloseEvent>,
     mut leave_event_writer: EventWriter<LeaveEvent>,
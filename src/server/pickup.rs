@@ -0,0 +1,222 @@
+use bevy::math::Vec2;
+use bevy::prelude::{
+    EventReader, EventWriter, Query, Res, ResMut, Resource, Time, Transform, With,
+};
+use rand::prelude::SliceRandom;
+use rand::{thread_rng, Rng};
+use uuid::Uuid;
+
+use crate::behaviour::collideable::{CollisionEvent, CollisionKind, CollisionType};
+use crate::behaviour::damageable::Damageable;
+use crate::behaviour::weaponized::Weaponized;
+use crate::constants::{
+    HALF, PICKUP_CLEAR_RADIUS, PICKUP_COUNT_MAX, PICKUP_HEALTH_AMOUNT, PICKUP_SPAWN_ATTEMPTS,
+    PICKUP_SPAWN_RATE_SECONDS, PICKUP_TYPES,
+};
+use crate::identity::asteroid::Asteroid;
+use crate::identity::effect::{ApplyEffectEvent, Effect};
+use crate::identity::map::Map;
+use crate::identity::pickup::Pickup;
+use crate::identity::player::Player;
+use crate::identity::weapon::WEAPONS;
+use crate::types::event::{DespawnEvent, SerializableTransform, SpawnEvent};
+
+#[derive(Debug, Clone, Resource)]
+pub struct PickupSpawner {
+    pub last_spawned_at: f64,
+}
+
+// somewhere at least PICKUP_CLEAR_RADIUS from anything in the way, if one can be found; maps with
+// pickup spawners only ever use those
+fn get_clear_translation(map: &Map, occupied: &[Vec2]) -> Option<Vec2> {
+    let mut rng = thread_rng();
+    let extents = map.size * HALF - Vec2::splat(PICKUP_CLEAR_RADIUS);
+
+    let is_clear = |translation: Vec2| {
+        occupied
+            .iter()
+            .all(|other| other.distance(translation) >= PICKUP_CLEAR_RADIUS)
+    };
+
+    if !map.pickup_spawners.is_empty() {
+        let mut pickup_spawners = map.pickup_spawners.clone();
+        pickup_spawners.shuffle(&mut rng);
+
+        return pickup_spawners
+            .into_iter()
+            .find(|translation| is_clear(*translation));
+    }
+
+    for _ in 0..PICKUP_SPAWN_ATTEMPTS {
+        let translation = Vec2::new(
+            (rng.gen::<f32>() * 2.0 - 1.0) * extents.x,
+            (rng.gen::<f32>() * 2.0 - 1.0) * extents.y,
+        );
+
+        if is_clear(translation) && map.is_clear(translation, PICKUP_CLEAR_RADIUS) {
+            return Some(translation);
+        }
+    }
+
+    None
+}
+
+pub fn handle_pickup_spawner(
+    time: Res<Time>,
+    map: Res<Map>,
+    mut pickup_spawner: ResMut<PickupSpawner>,
+    pickup_query: Query<(&Pickup, &Transform)>,
+    player_query: Query<&Transform, With<Player>>,
+    asteroid_query: Query<&Transform, With<Asteroid>>,
+    mut spawn_event_writer: EventWriter<SpawnEvent>,
+) {
+    if time.elapsed_seconds_f64() - pickup_spawner.last_spawned_at < PICKUP_SPAWN_RATE_SECONDS {
+        return;
+    }
+
+    pickup_spawner.last_spawned_at = time.elapsed_seconds_f64();
+
+    if pickup_query.iter().count() >= PICKUP_COUNT_MAX {
+        return;
+    }
+
+    let mut occupied = vec![];
+    for (_, transform) in pickup_query.iter() {
+        occupied.push(transform.translation.truncate());
+    }
+    for transform in player_query.iter() {
+        occupied.push(transform.translation.truncate());
+    }
+    for transform in asteroid_query.iter() {
+        occupied.push(transform.translation.truncate());
+    }
+
+    let translation = get_clear_translation(&map, &occupied);
+    if translation.is_none() {
+        return; // try again next time
+    }
+
+    let pickup_type = PICKUP_TYPES.choose(&mut thread_rng()).unwrap();
+
+    spawn_event_writer.send(SpawnEvent {
+        entity_uuid: Uuid::new_v4(),
+        entity_type: "pickup".to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(translation.unwrap().extend(0.0)),
+        )),
+        pickup_type: Some(pickup_type.to_string()),
+        ..Default::default()
+    });
+}
+
+// a pickup is only ever collected by actually flying through it; the server doesn't take collisions
+// from clients
+pub fn handle_collision_event_for_pickup(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut pickup_query: Query<&mut Pickup>,
+    mut player_query: Query<(&Player, &mut Damageable, &mut Weaponized)>,
+    mut apply_effect_event_writer: EventWriter<ApplyEffectEvent>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+) {
+    for collision_event in collision_event_reader.read() {
+        if collision_event.kind == CollisionKind::Separation {
+            continue;
+        }
+
+        let colliders = [
+            (&collision_event.collider_a, &collision_event.collider_b),
+            (&collision_event.collider_b, &collision_event.collider_a),
+        ];
+
+        for (collider, other_collider) in colliders.iter() {
+            if collider.collision_type != Some(CollisionType::Pickup)
+                || other_collider.collision_type != Some(CollisionType::Player)
+            {
+                continue;
+            }
+
+            let pickup = pickup_query
+                .iter_mut()
+                .find(|pickup| pickup.pickup_uuid == collider.entity_uuid);
+
+            if pickup.is_none() {
+                continue;
+            }
+
+            let mut pickup = pickup.unwrap();
+            if pickup.is_collected {
+                continue;
+            }
+
+            let player = player_query
+                .iter_mut()
+                .find(|(player, _, _)| player.player_uuid == other_collider.entity_uuid);
+
+            if player.is_none() {
+                continue;
+            }
+
+            let (player, mut damageable, mut weaponized) = player.unwrap();
+
+            if pickup.pickup_type == "health" {
+                damageable.heal(PICKUP_HEALTH_AMOUNT);
+            } else if pickup.pickup_type == "weapon" {
+                // something not already held, if there's anything left
+                let weapon_types: Vec<&str> = WEAPONS
+                    .iter()
+                    .map(|weapon| weapon.weapon_type)
+                    .filter(|weapon_type| {
+                        !weaponized
+                            .weapon_types
+                            .iter()
+                            .any(|held_weapon_type| held_weapon_type == weapon_type)
+                    })
+                    .collect();
+
+                let weapon_type = weapon_types
+                    .choose(&mut thread_rng())
+                    .copied()
+                    .unwrap_or(WEAPONS.choose(&mut thread_rng()).unwrap().weapon_type);
+
+                weaponized.pick_up_weapon(weapon_type);
+            } else {
+                apply_effect_event_writer.send(ApplyEffectEvent {
+                    player_uuid: player.player_uuid,
+                    effect_type: pickup.pickup_type.clone(),
+                });
+            }
+
+            pickup.is_collected = true;
+
+            despawn_event_writer.send(DespawnEvent {
+                entity_uuid: pickup.pickup_uuid,
+                entity_type: "pickup".to_string(),
+            });
+        }
+    }
+}
+
+// clean slate for each round; runs on entering the countdown
+pub fn handle_round_reset_for_pickup(
+    time: Res<Time>,
+    mut pickup_spawner: ResMut<PickupSpawner>,
+    pickup_query: Query<&Pickup>,
+    effect_query: Query<&Effect>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+) {
+    for pickup in pickup_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: pickup.pickup_uuid,
+            entity_type: "pickup".to_string(),
+        });
+    }
+
+    for effect in effect_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: effect.effect_uuid,
+            entity_type: "effect".to_string(),
+        });
+    }
+
+    pickup_spawner.last_spawned_at = time.elapsed_seconds_f64();
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::{EventReader, EventWriter, Query, Res, Time};
+
+use crate::base::helpers::serialize;
+use crate::identity::player::Player;
+use crate::types::event::{PingEvent, PongEvent};
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+pub fn handle_ping_event(
+    mut ping_event_reader: EventReader<PingEvent>,
+    mut player_query: Query<&mut Player>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+    time: Res<Time>,
+) {
+    for ping in ping_event_reader.read() {
+        for mut player in player_query.iter_mut() {
+            if player.player_uuid != ping.player_uuid {
+                continue;
+            }
+
+            if ping.last_round_trip_time.is_some() {
+                player.ping = ping.last_round_trip_time;
+            }
+        }
+
+        // tell the pinger when we got it
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: Some(ping.player_uuid),
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "pong".to_string(),
+                pong: Some(PongEvent {
+                    player_uuid: ping.player_uuid,
+                    client_time: ping.client_time,
+                    server_time: time.elapsed_seconds_f64(),
+                }),
+                ..Default::default()
+            }),
+        });
+    }
+}
@@ -0,0 +1,59 @@
+use bevy::math::{Quat, Vec2};
+use bevy::prelude::{Query, Res, Time, Transform, With, Without};
+use bevy_rapier2d::dynamics::Velocity;
+
+use crate::behaviour::weaponized::Weaponized;
+use crate::constants::WEAPON_HOMING_RANGE;
+use crate::identity::player::Player;
+use crate::identity::projectile::Projectile;
+use crate::identity::weapon::get_weapon;
+
+pub fn handle_homing_projectile(
+    time: Res<Time>,
+    mut projectile_query: Query<(&Projectile, &mut Transform, &mut Velocity), Without<Player>>,
+    player_query: Query<(&Transform, &Weaponized), With<Player>>,
+) {
+    for (projectile, mut transform, mut velocity) in projectile_query.iter_mut() {
+        let weapon = get_weapon(&projectile.weapon_type);
+        if weapon.homing_degrees_per_second.is_none() {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+
+        // the nearest player in range that didn't fire it
+        let mut target: Option<Vec2> = None;
+        for (player_transform, weaponized) in player_query.iter() {
+            if weaponized.weapon_uuid == projectile.weapon_uuid {
+                continue;
+            }
+
+            let player_position = player_transform.translation.truncate();
+            let distance = position.distance(player_position);
+
+            if distance > WEAPON_HOMING_RANGE {
+                continue;
+            }
+
+            if target.is_none() || distance < position.distance(target.unwrap()) {
+                target = Some(player_position);
+            }
+        }
+
+        if target.is_none() || velocity.linvel.length() == 0.0 {
+            continue;
+        }
+
+        let heading = velocity.linvel;
+        let desired = target.unwrap() - position;
+
+        let max_turn =
+            f32::to_radians(weapon.homing_degrees_per_second.unwrap()) * time.delta_seconds();
+        let turn = heading.angle_between(desired).clamp(-max_turn, max_turn);
+
+        let rotation = Quat::from_rotation_z(turn);
+
+        velocity.linvel = rotation.mul_vec3(heading.extend(0.0)).truncate();
+        transform.rotation = rotation * transform.rotation;
+    }
+}
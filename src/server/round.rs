@@ -0,0 +1,294 @@
+use std::env;
+use std::str::FromStr;
+
+use bevy::prelude::{
+    trace, EventReader, EventWriter, NextState, Query, Res, ResMut, State, StateTransitionEvent,
+    Time, Transform,
+};
+use bevy_rapier2d::dynamics::Velocity;
+
+use crate::base::helpers::serialize;
+use crate::behaviour::damageable::Damageable;
+use crate::behaviour::moveable::Moveable;
+use crate::constants::{
+    ROUND_COUNTDOWN_SECONDS, ROUND_DURATION_SECONDS, ROUND_FRIENDLY_FIRE, ROUND_GAME_MODE,
+    ROUND_MIN_PLAYERS, ROUND_RESULTS_SECONDS, ROUND_SCORE_LIMIT, ROUND_TEAM_COUNT, TEAM_NAMES,
+};
+use crate::identity::game::Game;
+use crate::identity::game_mode::{get_game_mode, Objectives};
+use crate::identity::map::Map;
+use crate::identity::player::Player;
+use crate::identity::projectile::Projectile;
+use crate::identity::round::{MatchRules, MatchState, Round};
+use crate::identity::team::get_team_scores;
+use crate::server::damage::respawn_player;
+use crate::types::event::{DespawnEvent, JoinEvent, RoundEvent};
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+fn get_env<T: FromStr>(name: &str, default: T) -> T {
+    let value = env::var(name);
+    if value.is_err() {
+        return default;
+    }
+
+    value.unwrap().parse::<T>().unwrap_or(default)
+}
+
+// rules can be overridden with env vars; a duration or score limit of 0 disables that limit
+pub fn get_match_rules() -> MatchRules {
+    let round_duration_seconds = get_env("ROUND_DURATION_SECONDS", ROUND_DURATION_SECONDS);
+    let game_mode = get_game_mode(&get_env("ROUND_GAME_MODE", ROUND_GAME_MODE.to_string()));
+
+    // modes with an objective are won by the objective rather than the score
+    let mut score_limit = get_env("ROUND_SCORE_LIMIT", ROUND_SCORE_LIMIT);
+    if game_mode.objective_name.is_some() {
+        score_limit = 0;
+    }
+
+    let mut team_count = get_env("ROUND_TEAM_COUNT", ROUND_TEAM_COUNT).min(TEAM_NAMES.len() as u32);
+    if game_mode.is_team_only {
+        team_count = team_count.max(2);
+    }
+
+    MatchRules {
+        min_players: get_env("ROUND_MIN_PLAYERS", ROUND_MIN_PLAYERS).max(1),
+        countdown_seconds: get_env("ROUND_COUNTDOWN_SECONDS", ROUND_COUNTDOWN_SECONDS),
+        round_duration_seconds: if round_duration_seconds > 0.0 {
+            Some(round_duration_seconds)
+        } else {
+            None
+        },
+        score_limit: if score_limit > 0 {
+            Some(score_limit)
+        } else {
+            None
+        },
+        results_seconds: get_env("ROUND_RESULTS_SECONDS", ROUND_RESULTS_SECONDS),
+        team_count,
+        is_friendly_fire: get_env("ROUND_FRIENDLY_FIRE", ROUND_FRIENDLY_FIRE),
+        game_mode: game_mode.game_mode.to_string(),
+    }
+}
+
+pub fn handle_round(
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    objectives: Res<Objectives>,
+    game: Res<Game>,
+    round: Res<Round>,
+    match_state: Res<State<MatchState>>,
+    mut next_match_state: ResMut<NextState<MatchState>>,
+    player_query: Query<&Player>,
+) {
+    let now = time.elapsed_seconds_f64();
+    let player_count = game.player_uuids.len();
+    let has_ended = round.ends_at.is_some() && now >= round.ends_at.unwrap();
+
+    match match_state.get() {
+        MatchState::WaitingForPlayers => {
+            if player_count >= rules.min_players {
+                next_match_state.set(MatchState::Countdown);
+            }
+        }
+        MatchState::Countdown => {
+            if player_count < rules.min_players {
+                next_match_state.set(MatchState::WaitingForPlayers);
+            } else if has_ended {
+                next_match_state.set(MatchState::InRound);
+            }
+        }
+        MatchState::InRound => {
+            // in team rounds it's the team total that counts
+            let team_scores = get_team_scores(rules.team_count, player_query.iter());
+
+            let is_score_limit_reached = rules.score_limit.is_some()
+                && if team_scores.is_empty() {
+                    player_query
+                        .iter()
+                        .any(|player| player.score >= rules.score_limit.unwrap())
+                } else {
+                    team_scores
+                        .iter()
+                        .any(|team_score| team_score.score >= rules.score_limit.unwrap())
+                };
+
+            let game_mode = get_game_mode(&rules.game_mode);
+            let is_objective_reached = game_mode.objective_name.is_some()
+                && objectives
+                    .scores
+                    .iter()
+                    .any(|score| *score >= game_mode.objective_limit);
+
+            // too many have left to carry on, so it ends with whoever's still there
+            if player_count < rules.min_players
+                || has_ended
+                || is_score_limit_reached
+                || is_objective_reached
+            {
+                next_match_state.set(MatchState::Results);
+            }
+        }
+        MatchState::Results => {
+            if has_ended {
+                next_match_state.set(MatchState::WaitingForPlayers);
+            }
+        }
+    }
+}
+
+pub fn handle_match_state_transition(
+    mut state_transition_event_reader: EventReader<StateTransitionEvent<MatchState>>,
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    objectives: Res<Objectives>,
+    mut round: ResMut<Round>,
+    player_query: Query<&Player>,
+    mut round_event_writer: EventWriter<RoundEvent>,
+) {
+    for state_transition_event in state_transition_event_reader.read() {
+        if state_transition_event.entered.is_none() {
+            continue;
+        }
+
+        let match_state = state_transition_event.entered.unwrap();
+        let now = time.elapsed_seconds_f64();
+
+        round.match_state = match_state;
+        round.started_at = now;
+        round.min_players = rules.min_players;
+        round.score_limit = rules.score_limit;
+
+        match match_state {
+            MatchState::WaitingForPlayers => {
+                round.ends_at = None;
+                round.winner_uuid = None;
+                round.winner_team = None;
+            }
+            MatchState::Countdown => {
+                round.ends_at = Some(now + rules.countdown_seconds);
+                round.winner_uuid = None;
+                round.winner_team = None;
+            }
+            MatchState::InRound => {
+                // only counted once it actually starts, so aborted countdowns don't skip numbers
+                round.round_number += 1;
+                round.ends_at = rules.round_duration_seconds.map(|seconds| now + seconds);
+            }
+            MatchState::Results => {
+                round.ends_at = Some(now + rules.results_seconds);
+
+                // a tie for first place is a draw
+                let mut players: Vec<&Player> = player_query.iter().collect();
+                players.sort_by(|a, b| b.score.cmp(&a.score));
+
+                round.winner_uuid = None;
+                if !players.is_empty()
+                    && (players.len() == 1 || players[0].score > players[1].score)
+                {
+                    round.winner_uuid = Some(players[0].player_uuid);
+                }
+
+                let mut team_scores = get_team_scores(rules.team_count, player_query.iter());
+                team_scores.sort_by(|a, b| b.score.cmp(&a.score));
+
+                // in team rounds the best player doesn't win on their own
+                round.winner_team = None;
+                if !team_scores.is_empty() {
+                    round.winner_uuid = None;
+
+                    if team_scores.len() == 1 || team_scores[0].score > team_scores[1].score {
+                        round.winner_team = Some(team_scores[0].team);
+                    }
+                }
+
+                // and in modes with an objective, it's the objective that counts
+                if get_game_mode(&rules.game_mode).objective_name.is_some() {
+                    round.winner_team = objectives.get_leader();
+                }
+            }
+        }
+
+        trace!("handle_match_state_transition; round={:?}", round);
+
+        round_event_writer.send(RoundEvent {
+            server_time: now,
+            round: round.clone(),
+        });
+    }
+}
+
+// clean slate for each round; runs on entering the countdown
+pub fn handle_round_reset(
+    map: Res<Map>,
+    mut player_query: Query<(
+        &mut Player,
+        &mut Damageable,
+        &mut Moveable,
+        &mut Transform,
+        &mut Velocity,
+    )>,
+    projectile_query: Query<&Projectile>,
+    mut despawn_event_writer: EventWriter<DespawnEvent>,
+) {
+    for (mut player, mut damageable, mut moveable, mut transform, mut velocity) in
+        player_query.iter_mut()
+    {
+        player.score = 0;
+
+        respawn_player(
+            &map,
+            &mut damageable,
+            &mut moveable,
+            &mut transform,
+            &mut velocity,
+        );
+    }
+
+    for projectile in projectile_query.iter() {
+        despawn_event_writer.send(DespawnEvent {
+            entity_uuid: projectile.projectile_uuid,
+            entity_type: "projectile".to_string(),
+        });
+    }
+}
+
+pub fn handle_round_event(
+    mut round_event_reader: EventReader<RoundEvent>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for round_event in round_event_reader.read() {
+        // tell everyone about the round
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: None,
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "round".to_string(),
+                round: Some(round_event.clone()),
+                ..Default::default()
+            }),
+        });
+    }
+}
+
+pub fn handle_join_event_for_round(
+    mut join_event_reader: EventReader<JoinEvent>,
+    round: Res<Round>,
+    time: Res<Time>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for join_event in join_event_reader.read() {
+        // tell the joiner where the round is up to
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: Some(join_event.player_uuid),
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "round".to_string(),
+                round: Some(RoundEvent {
+                    server_time: time.elapsed_seconds_f64(),
+                    round: round.clone(),
+                }),
+                ..Default::default()
+            }),
+        });
+    }
+}
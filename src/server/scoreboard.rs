@@ -0,0 +1,76 @@
+use bevy::prelude::{EventReader, EventWriter, Query, Res, ResMut, Time};
+
+use crate::base::helpers::serialize;
+use crate::behaviour::damageable::Damageable;
+use crate::constants::SCOREBOARD_NETWORK_UPDATE_RATE_SECONDS;
+use crate::identity::player::Player;
+use crate::identity::round::MatchRules;
+use crate::identity::scoreboard::Scoreboard;
+use crate::identity::team::get_team_scores;
+use crate::types::event::{ScoreboardEntry, ScoreboardEvent};
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+pub fn handle_scoreboard(
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    mut scoreboard: ResMut<Scoreboard>,
+    player_query: Query<(&Player, &Damageable)>,
+    mut scoreboard_event_writer: EventWriter<ScoreboardEvent>,
+) {
+    if time.elapsed_seconds_f64() - scoreboard.last_update_at
+        < SCOREBOARD_NETWORK_UPDATE_RATE_SECONDS
+    {
+        return;
+    }
+
+    let mut entries = vec![];
+
+    for (player, damageable) in player_query.iter() {
+        entries.push(ScoreboardEntry {
+            player_uuid: player.player_uuid,
+            name: player.name.clone(),
+            color: player.color,
+            score: player.score,
+            health: damageable.health,
+            max_health: damageable.max_health,
+            ping: player.ping,
+            team: player.team,
+        });
+    }
+
+    entries.sort_by(|a, b| b.score.cmp(&a.score));
+
+    let mut teams = get_team_scores(
+        rules.team_count,
+        player_query.iter().map(|(player, _)| player),
+    );
+    teams.sort_by(|a, b| b.score.cmp(&a.score));
+
+    scoreboard.entries = entries.clone();
+    scoreboard.teams = teams.clone();
+    scoreboard.last_update_at = time.elapsed_seconds_f64();
+
+    scoreboard_event_writer.send(ScoreboardEvent {
+        server_time: time.elapsed_seconds_f64(),
+        entries,
+        teams,
+    });
+}
+
+pub fn handle_scoreboard_event(
+    mut scoreboard_event_reader: EventReader<ScoreboardEvent>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for scoreboard_event in scoreboard_event_reader.read() {
+        // tell everyone about the scores
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: None,
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "scoreboard".to_string(),
+                scoreboard: Some(scoreboard_event.clone()),
+                ..Default::default()
+            }),
+        });
+    }
+}
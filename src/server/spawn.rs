@@ -11,13 +11,8 @@ pub fn handle_spawn_event(
     for spawn_event in spawn_event_reader.read() {
         let message = serialize(Container {
             message_type: "spawn".to_string(),
-            join: None,
             spawn: Some(spawn_event.clone()),
-            update: None,
-            input: None,
-            despawn: None,
-            leave: None,
-            collision: None,
+            ..Default::default()
         });
 
         // tell everyone to spawn the entity
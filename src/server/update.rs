@@ -14,13 +14,8 @@ pub fn handle_update_event(
             not_session_uuid: None,
             message: serialize(Container {
                 message_type: "update".to_string(),
-                join: None,
-                spawn: None,
-                input: None,
                 update: Some(update.clone()),
-                despawn: None,
-                leave: None,
-                collision: None,
+                ..Default::default()
             }),
         });
     }
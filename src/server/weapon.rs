@@ -0,0 +1,68 @@
+use bevy::prelude::{EventReader, EventWriter, Query, Res, Time};
+
+use crate::base::helpers::serialize;
+use crate::behaviour::weaponized::Weaponized;
+use crate::constants::WEAPON_NETWORK_UPDATE_RATE_SECONDS;
+use crate::identity::player::Player;
+use crate::types::event::WeaponEvent;
+use crate::types::network::{Container, OutgoingMessageEvent};
+
+pub fn handle_weaponized(
+    time: Res<Time>,
+    mut weaponized_query: Query<(&Player, &mut Weaponized)>,
+    mut weapon_event_writer: EventWriter<WeaponEvent>,
+) {
+    let now = time.elapsed_seconds_f64();
+
+    for (player, mut weaponized) in weaponized_query.iter_mut() {
+        weaponized.cool_and_reload(now, time.delta_seconds());
+
+        if now - weaponized.last_update_at < WEAPON_NETWORK_UPDATE_RATE_SECONDS {
+            continue;
+        }
+
+        let weapon = weaponized.get_weapon();
+
+        let mut ammo = None;
+        if weapon.magazine_size.is_some() {
+            ammo = Some(weaponized.ammo[weaponized.weapon_index]);
+        }
+
+        let mut heat = None;
+        if weapon.heat_per_shot.is_some() {
+            heat = Some(weaponized.heat);
+        }
+
+        weapon_event_writer.send(WeaponEvent {
+            player_uuid: player.player_uuid,
+            server_time: now,
+            weapon_type: weapon.weapon_type.to_string(),
+            weapon_types: weaponized.weapon_types.clone(),
+            ammo,
+            magazine_size: weapon.magazine_size,
+            reload_progress: weaponized.get_reload_progress(now),
+            heat,
+            is_overheated: weaponized.is_overheated(now),
+        });
+
+        weaponized.last_update_at = now;
+    }
+}
+
+pub fn handle_weapon_event(
+    mut weapon_event_reader: EventReader<WeaponEvent>,
+    mut outgoing_message_event_writer: EventWriter<OutgoingMessageEvent>,
+) {
+    for weapon_event in weapon_event_reader.read() {
+        // nobody else needs to know how much ammo you've got
+        outgoing_message_event_writer.send(OutgoingMessageEvent {
+            session_uuid: Some(weapon_event.player_uuid),
+            not_session_uuid: None,
+            message: serialize(Container {
+                message_type: "weapon".to_string(),
+                weapon: Some(weapon_event.clone()),
+                ..Default::default()
+            }),
+        });
+    }
+}
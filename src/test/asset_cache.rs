@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+use bevy::asset::{AssetApp, AssetPlugin, Assets};
+use bevy::math::Vec3;
+use bevy::prelude::{
+    App, Color, ColorMaterial, IntoSystemConfigs, Mesh, MinimalPlugins, Startup, Transform, Update,
+};
+use bevy_rapier2d::dynamics::Velocity;
+use uuid::Uuid;
+
+use crate::base::despawn::base_handle_despawn_event;
+use crate::base::spawn::base_handle_spawn_event;
+use crate::constants::{ASTEROID_COLOR, ASTEROID_SIZE_MAX, BOUNDS};
+use crate::identity::asset_cache::{handle_asset_cache, handle_asset_cache_setup, AssetCache};
+use crate::identity::entity::get_entity_kinds;
+use crate::identity::game::Game;
+use crate::identity::weapon::WEAPONS;
+use crate::types::event::{DespawnEvent, SerializableTransform, SerializableVelocity, SpawnEvent};
+
+const SPAWN_COUNT: usize = 3000;
+const MESH_COUNT_MAX: usize = 64;
+const MATERIAL_COUNT_MAX: usize = 32;
+
+fn get_app() -> App {
+    let mut app = App::new();
+
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    app.init_asset::<Mesh>();
+    app.init_asset::<ColorMaterial>();
+
+    app.insert_resource(Game {
+        role: "client".to_string(),
+        local_player_uuid: None,
+        player_uuids: HashSet::new(),
+        last_update: 0.0,
+        server_time_at_join: 0.0,
+        client_time_at_join: 0.0,
+        is_connected: false,
+        last_ping_at: 0.0,
+        round_trip_time: None,
+        is_wrapping: true,
+        arena_size: BOUNDS,
+    });
+
+    app.insert_resource(get_entity_kinds());
+    app.insert_resource(AssetCache::default());
+
+    app.add_event::<SpawnEvent>();
+    app.add_event::<DespawnEvent>();
+
+    app.add_systems(Startup, handle_asset_cache_setup);
+    app.add_systems(
+        Update,
+        (
+            base_handle_spawn_event,
+            base_handle_despawn_event,
+            handle_asset_cache,
+        )
+            .chain(),
+    );
+
+    app.update();
+
+    app
+}
+
+fn get_spawn_event(i: usize) -> SpawnEvent {
+    let transform = Some(SerializableTransform::from_transform(
+        Transform::from_translation(Vec3::ZERO),
+    ));
+    let velocity = Some(SerializableVelocity::from_velocity(Velocity::zero()));
+
+    // a handful of players, and plenty of projectiles and asteroids
+    let colors = [
+        Color::srgb(1.0, 0.0, 0.0),
+        Color::srgb(0.0, 1.0, 0.0),
+        Color::srgb(0.0, 0.0, 1.0),
+    ];
+
+    match i % 3 {
+        0 => SpawnEvent {
+            entity_uuid: Uuid::new_v4(),
+            entity_type: "player".to_string(),
+            transform,
+            velocity,
+            color: Some(colors[i % colors.len()]),
+            ..Default::default()
+        },
+        1 => {
+            let weapon = &WEAPONS[i % WEAPONS.len()];
+
+            SpawnEvent {
+                entity_uuid: Uuid::new_v4(),
+                entity_type: "projectile".to_string(),
+                transform,
+                velocity,
+                color: Some(weapon.color),
+                weapon_uuid: Some(Uuid::new_v4()),
+                weapon_type: Some(weapon.weapon_type.to_string()),
+                charge: Some((i % 10) as f32 / 9.0),
+                ..Default::default()
+            }
+        }
+        _ => SpawnEvent {
+            entity_uuid: Uuid::new_v4(),
+            entity_type: "asteroid".to_string(),
+            transform,
+            velocity,
+            color: Some(ASTEROID_COLOR),
+            size: Some((i as u32 % ASTEROID_SIZE_MAX) + 1),
+            ..Default::default()
+        },
+    }
+}
+
+// every player a colour nobody else has picked
+fn get_spawn_event_with_arbitrary_color(i: usize) -> SpawnEvent {
+    let f = i as f32 / SPAWN_COUNT as f32;
+
+    SpawnEvent {
+        entity_uuid: Uuid::new_v4(),
+        entity_type: "player".to_string(),
+        transform: Some(SerializableTransform::from_transform(
+            Transform::from_translation(Vec3::ZERO),
+        )),
+        velocity: Some(SerializableVelocity::from_velocity(Velocity::zero())),
+        color: Some(Color::srgb(f, 1.0 - f, (f * 7.0) % 1.0)),
+        ..Default::default()
+    }
+}
+
+fn assert_asset_counts_stay_bounded(get_spawn_event: fn(usize) -> SpawnEvent) {
+    let mut app = get_app();
+
+    for i in 0..SPAWN_COUNT {
+        let spawn_event = get_spawn_event(i);
+        let despawn_event = DespawnEvent {
+            entity_uuid: spawn_event.entity_uuid,
+            entity_type: spawn_event.entity_type.clone(),
+        };
+
+        app.world_mut().send_event(spawn_event);
+        app.update();
+
+        app.world_mut().send_event(despawn_event);
+        app.update();
+    }
+
+    // one more for the last of the dropped handles to be dealt with
+    app.update();
+
+    let mesh_count = app.world().resource::<Assets<Mesh>>().len();
+    let material_count = app.world().resource::<Assets<ColorMaterial>>().len();
+    let asset_cache = app.world().resource::<AssetCache>();
+
+    // everything that was added came from (and is still held by) the cache
+    assert_eq!(mesh_count, asset_cache.meshes.len());
+    assert_eq!(material_count, asset_cache.materials.len());
+
+    assert!(mesh_count <= MESH_COUNT_MAX, "mesh_count={:?}", mesh_count);
+    assert!(
+        material_count <= MATERIAL_COUNT_MAX,
+        "material_count={:?}",
+        material_count
+    );
+}
+
+#[test]
+fn test_asset_counts_stay_bounded() {
+    assert_asset_counts_stay_bounded(get_spawn_event);
+}
+
+#[test]
+fn test_asset_counts_stay_bounded_with_arbitrary_colors() {
+    assert_asset_counts_stay_bounded(get_spawn_event_with_arbitrary_color);
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+use bevy::prelude::Color;
+
+use crate::constants::{PLAYER_NAME_DEFAULT, PLAYER_NAME_LENGTH_MAX};
+use crate::server::hello::{sanitize_color, sanitize_name};
+
+fn get_taken_names(names: &[&str]) -> HashSet<String> {
+    names.iter().map(|name| name.to_lowercase()).collect()
+}
+
+#[test]
+fn test_sanitize_name_keeps_a_plain_name() {
+    assert_eq!(sanitize_name("Ed", &HashSet::new()), "Ed");
+    assert_eq!(sanitize_name("the_ed-2", &HashSet::new()), "the_ed-2");
+}
+
+#[test]
+fn test_sanitize_name_strips_unsupported_characters() {
+    assert_eq!(sanitize_name("<b>Ed</b>!", &HashSet::new()), "bEdb");
+    assert_eq!(sanitize_name("Éd 😀", &HashSet::new()), "d");
+}
+
+#[test]
+fn test_sanitize_name_collapses_whitespace() {
+    assert_eq!(sanitize_name("  Big   Ed  ", &HashSet::new()), "Big Ed");
+}
+
+#[test]
+fn test_sanitize_name_truncates() {
+    let name = sanitize_name(&"a".repeat(PLAYER_NAME_LENGTH_MAX * 2), &HashSet::new());
+
+    assert_eq!(name.chars().count(), PLAYER_NAME_LENGTH_MAX);
+}
+
+#[test]
+fn test_sanitize_name_falls_back_to_the_default() {
+    assert_eq!(sanitize_name("", &HashSet::new()), PLAYER_NAME_DEFAULT);
+    assert_eq!(sanitize_name("   ", &HashSet::new()), PLAYER_NAME_DEFAULT);
+    assert_eq!(sanitize_name("!!!", &HashSet::new()), PLAYER_NAME_DEFAULT);
+}
+
+#[test]
+fn test_sanitize_name_deduplicates_regardless_of_case() {
+    assert_eq!(sanitize_name("ed", &get_taken_names(&["Ed"])), "ed 2");
+    assert_eq!(
+        sanitize_name("Ed", &get_taken_names(&["ed", "ED 2"])),
+        "Ed 3"
+    );
+}
+
+#[test]
+fn test_sanitize_name_keeps_the_suffix_inside_the_length_limit() {
+    let long_name = "a".repeat(PLAYER_NAME_LENGTH_MAX);
+
+    let name = sanitize_name(&long_name, &get_taken_names(&[&long_name]));
+
+    assert_eq!(name.chars().count(), PLAYER_NAME_LENGTH_MAX);
+    assert!(name.ends_with(" 2"), "name={:?}", name);
+}
+
+#[test]
+fn test_sanitize_color_clamps_channels() {
+    assert_eq!(
+        sanitize_color(Color::srgb(-1.0, 0.5, 2.0)),
+        Color::srgb(0.0, 0.5, 1.0)
+    );
+}
+
+#[test]
+fn test_sanitize_color_zeroes_channels_that_arent_numbers() {
+    assert_eq!(
+        sanitize_color(Color::srgb(f32::NAN, f32::INFINITY, 0.5)),
+        Color::srgb(0.0, 0.0, 0.5)
+    );
+}
@@ -1 +1,6 @@
-
+#[cfg(test)]
+mod asset_cache;
+#[cfg(test)]
+mod hello;
+#[cfg(test)]
+mod network;
@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+
+use bevy::prelude::{App, Color, Event, Events, MinimalPlugins, Update};
+use uuid::Uuid;
+
+use crate::base::helpers::serialize;
+use crate::base::network::base_handle_incoming_message_event;
+use crate::behaviour::collideable::{Collider, CollisionEvent, CollisionKind, CollisionType};
+use crate::constants::BOUNDS;
+use crate::identity::game::Game;
+use crate::identity::round::Round;
+use crate::types::event::{
+    DespawnEvent, EffectEvent, GameModeEvent, HelloEvent, InputEvent, JoinEvent, KillEvent,
+    LeaveEvent, PingEvent, PongEvent, RoundEvent, ScoreboardEvent, SpawnEvent, UpdateEvent,
+    WeaponEvent,
+};
+use crate::types::network::{Container, IncomingMessageEvent};
+
+fn get_app(role: &str) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins);
+
+    app.insert_resource(Game {
+        role: role.to_string(),
+        local_player_uuid: None,
+        player_uuids: HashSet::new(),
+        last_update: 0.0,
+        server_time_at_join: 0.0,
+        client_time_at_join: 0.0,
+        is_connected: false,
+        last_ping_at: 0.0,
+        round_trip_time: None,
+        is_wrapping: true,
+        arena_size: BOUNDS,
+    });
+
+    app.add_event::<IncomingMessageEvent>();
+    app.add_event::<HelloEvent>();
+    app.add_event::<JoinEvent>();
+    app.add_event::<SpawnEvent>();
+    app.add_event::<InputEvent>();
+    app.add_event::<UpdateEvent>();
+    app.add_event::<LeaveEvent>();
+    app.add_event::<DespawnEvent>();
+    app.add_event::<CollisionEvent>();
+    app.add_event::<PingEvent>();
+    app.add_event::<PongEvent>();
+    app.add_event::<ScoreboardEvent>();
+    app.add_event::<KillEvent>();
+    app.add_event::<RoundEvent>();
+    app.add_event::<WeaponEvent>();
+    app.add_event::<GameModeEvent>();
+    app.add_event::<EffectEvent>();
+
+    app.add_systems(Update, base_handle_incoming_message_event);
+
+    app
+}
+
+fn send_message(app: &mut App, container: Container) {
+    app.world_mut().send_event(IncomingMessageEvent {
+        session_uuid: Uuid::new_v4(),
+        message: serialize(container),
+    });
+
+    app.update();
+}
+
+fn get_event_count<E: Event>(app: &App) -> usize {
+    app.world().resource::<Events<E>>().len()
+}
+
+fn get_kill_container() -> Container {
+    Container {
+        message_type: "kill".to_string(),
+        kill: Some(KillEvent {
+            killer_uuid: Some(Uuid::new_v4()),
+            victim_uuid: Uuid::new_v4(),
+            server_time: 0.0,
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_server_takes_hello_input_and_ping_from_clients() {
+    let mut app = get_app("server");
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "hello".to_string(),
+            hello: Some(HelloEvent {
+                session_uuid: Uuid::new_v4(),
+                name: "Ed".to_string(),
+                color: Color::WHITE,
+            }),
+            ..Default::default()
+        },
+    );
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "input".to_string(),
+            input: Some(InputEvent::new(Uuid::new_v4(), 0.0, 1.0, 0)),
+            ..Default::default()
+        },
+    );
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "ping".to_string(),
+            ping: Some(PingEvent {
+                player_uuid: Uuid::new_v4(),
+                client_time: 0.0,
+                last_round_trip_time: None,
+            }),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(get_event_count::<HelloEvent>(&app), 1);
+    assert_eq!(get_event_count::<InputEvent>(&app), 1);
+    assert_eq!(get_event_count::<PingEvent>(&app), 1);
+}
+
+#[test]
+fn test_server_drops_kills_and_scoreboards_from_clients() {
+    let mut app = get_app("server");
+
+    send_message(&mut app, get_kill_container());
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "scoreboard".to_string(),
+            scoreboard: Some(ScoreboardEvent {
+                server_time: 0.0,
+                entries: vec![],
+                teams: vec![],
+            }),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(get_event_count::<KillEvent>(&app), 0);
+    assert_eq!(get_event_count::<ScoreboardEvent>(&app), 0);
+}
+
+#[test]
+fn test_server_drops_joins_from_clients() {
+    let mut app = get_app("server");
+
+    // joining goes through hello, which is where names and colours get sanitised
+    send_message(
+        &mut app,
+        Container {
+            message_type: "join".to_string(),
+            join: Some(JoinEvent {
+                player_uuid: Uuid::new_v4(),
+                is_for_local_player: false,
+                server_time: 0.0,
+                name: "<b>Ed</b>".to_string(),
+                color: Color::srgb(f32::NAN, 2.0, -1.0),
+                team: None,
+                is_wrapping: true,
+                arena_size: BOUNDS,
+            }),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(get_event_count::<JoinEvent>(&app), 0);
+}
+
+#[test]
+fn test_server_drops_rounds_from_clients() {
+    let mut app = get_app("server");
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "round".to_string(),
+            round: Some(RoundEvent {
+                server_time: 0.0,
+                round: Round::default(),
+            }),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(get_event_count::<RoundEvent>(&app), 0);
+}
+
+fn get_collider(entity_type: &str, collision_type: CollisionType) -> Collider {
+    Collider {
+        entity_uuid: Uuid::new_v4(),
+        entity_type: entity_type.to_string(),
+        collision_type: Some(collision_type),
+        transform: None,
+        velocity: None,
+    }
+}
+
+#[test]
+fn test_server_drops_collisions_from_clients() {
+    let mut app = get_app("server");
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "collision".to_string(),
+            collision: Some(CollisionEvent {
+                collider_a: get_collider("asteroid", CollisionType::Asteroid),
+                collider_b: get_collider("projectile", CollisionType::Projectile),
+                kind: CollisionKind::Contact,
+            }),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(get_event_count::<CollisionEvent>(&app), 0);
+}
+
+#[test]
+fn test_server_drops_effects_from_clients() {
+    let mut app = get_app("server");
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "effect".to_string(),
+            effect: Some(EffectEvent {
+                effect_uuid: Uuid::new_v4(),
+                magnitude: 1.0,
+            }),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(get_event_count::<EffectEvent>(&app), 0);
+}
+
+#[test]
+fn test_server_drops_game_modes_from_clients() {
+    let mut app = get_app("server");
+
+    send_message(
+        &mut app,
+        Container {
+            message_type: "game_mode".to_string(),
+            game_mode: Some(GameModeEvent {
+                server_time: 0.0,
+                game_mode: "ctf".to_string(),
+                objective_name: Some("captures".to_string()),
+                objective_limit: 3.0,
+                objectives: vec![3.0, 0.0],
+                flags: vec![],
+                zone_team: None,
+            }),
+            ..Default::default()
+        },
+    );
+
+    // a forged kill would otherwise make a real flag carrier drop the flag
+    send_message(&mut app, get_kill_container());
+
+    assert_eq!(get_event_count::<GameModeEvent>(&app), 0);
+    assert_eq!(get_event_count::<KillEvent>(&app), 0);
+}
+
+#[test]
+fn test_client_takes_kills_from_the_server() {
+    let mut app = get_app("client");
+
+    send_message(&mut app, get_kill_container());
+
+    assert_eq!(get_event_count::<KillEvent>(&app), 1);
+}
+
+#[test]
+fn test_messages_missing_their_payload_are_skipped() {
+    let mut app = get_app("client");
+
+    for message_type in ["kill", "round", "effect", "game_mode"] {
+        send_message(
+            &mut app,
+            Container {
+                message_type: message_type.to_string(),
+                ..Default::default()
+            },
+        );
+    }
+
+    assert_eq!(get_event_count::<KillEvent>(&app), 0);
+    assert_eq!(get_event_count::<RoundEvent>(&app), 0);
+    assert_eq!(get_event_count::<EffectEvent>(&app), 0);
+    assert_eq!(get_event_count::<GameModeEvent>(&app), 0);
+}
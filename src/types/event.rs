@@ -3,11 +3,29 @@ use bevy_rapier2d::prelude::Velocity;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::constants::{INPUT_AXIS_STEPS, INPUT_BUTTON_FIRE, INPUT_BUTTON_SWITCH_WEAPON};
+use crate::identity::round::Round;
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct HelloEvent {
+    // filled in by the server from the session the hello arrived on
+    pub session_uuid: Uuid,
+    pub name: String,
+    pub color: Color,
+}
+
 #[derive(Event, Debug, Clone, Serialize, Deserialize)]
 pub struct JoinEvent {
     pub player_uuid: Uuid,
     pub is_for_local_player: bool,
     pub server_time: f64,
+    pub name: String,
+    pub color: Color,
+    // None for free-for-all
+    pub team: Option<u32>,
+    // false when the map has solid edges, so there's nothing to draw on the other side
+    pub is_wrapping: bool,
+    pub arena_size: Vec2,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,7 +79,7 @@ impl SerializableVelocity {
     }
 }
 
-#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+#[derive(Event, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SpawnEvent {
     pub entity_uuid: Uuid,
     // e.g. "player", "projectile" etc
@@ -69,16 +87,100 @@ pub struct SpawnEvent {
     pub transform: Option<SerializableTransform>,
     pub velocity: Option<SerializableVelocity>,
     pub color: Option<Color>,
+    pub name: Option<String>,
+    // the weapon that fired this entity (if it's a projectile)
+    pub weapon_uuid: Option<Uuid>,
+    // 1 (smallest) and up (if it's an asteroid)
+    pub size: Option<u32>,
+    // what kind of weapon fired this entity and how charged it was (if it's a projectile)
+    pub weapon_type: Option<String>,
+    pub charge: Option<f32>,
+    // e.g. "shield", "speed" etc (if it's a pickup or an effect)
+    pub pickup_type: Option<String>,
+    // the player an effect applies to (if it's an effect) or that fired it (if it's a projectile)
+    pub player_uuid: Option<Uuid>,
+    // None for free-for-all (if it's a player)
+    pub team: Option<u32>,
+    // the outline (if it's a wall)
+    pub points: Option<Vec<Vec2>>,
+    // what it's got left (if it's an effect), for anybody joining after it's worn down
+    pub magnitude: Option<f32>,
 }
 
+// the axes are quantized, to keep messages small and so that a stick wobbling a tiny bit isn't a
+// change worth sending
 #[derive(Event, Debug, Clone, Serialize, Deserialize)]
 pub struct InputEvent {
     pub player_uuid: Uuid,
-    pub is_left: bool,
-    pub is_right: bool,
-    pub is_forward: bool,
-    pub is_backward: bool,
-    pub is_firing: bool,
+    // -INPUT_AXIS_STEPS (all the way left) to INPUT_AXIS_STEPS (all the way right)
+    pub turn: i8,
+    // -INPUT_AXIS_STEPS (all the way backward) to INPUT_AXIS_STEPS (all the way forward)
+    pub thrust: i8,
+    // INPUT_BUTTON_* bits
+    pub buttons: u8,
+}
+
+pub fn get_quantized_axis(value: f32) -> i8 {
+    (value.clamp(-1.0, 1.0) * INPUT_AXIS_STEPS).round() as i8
+}
+
+pub fn get_unquantized_axis(value: i8) -> f32 {
+    (value as f32 / INPUT_AXIS_STEPS).clamp(-1.0, 1.0)
+}
+
+impl InputEvent {
+    pub fn new(player_uuid: Uuid, turn: f32, thrust: f32, buttons: u8) -> InputEvent {
+        InputEvent {
+            player_uuid,
+            turn: get_quantized_axis(turn),
+            thrust: get_quantized_axis(thrust),
+            buttons,
+        }
+    }
+
+    // keys (or anything else that's just on or off) go all the way
+    pub fn from_digital(
+        player_uuid: Uuid,
+        is_left: bool,
+        is_right: bool,
+        is_forward: bool,
+        is_backward: bool,
+        is_firing: bool,
+        is_switching_weapon: bool,
+    ) -> InputEvent {
+        let mut buttons = 0;
+
+        if is_firing {
+            buttons |= INPUT_BUTTON_FIRE;
+        }
+
+        if is_switching_weapon {
+            buttons |= INPUT_BUTTON_SWITCH_WEAPON;
+        }
+
+        InputEvent::new(
+            player_uuid,
+            is_right as i32 as f32 - is_left as i32 as f32,
+            is_forward as i32 as f32 - is_backward as i32 as f32,
+            buttons,
+        )
+    }
+
+    pub fn get_turn(self: &InputEvent) -> f32 {
+        get_unquantized_axis(self.turn)
+    }
+
+    pub fn get_thrust(self: &InputEvent) -> f32 {
+        get_unquantized_axis(self.thrust)
+    }
+
+    pub fn is_firing(self: &InputEvent) -> bool {
+        self.buttons & INPUT_BUTTON_FIRE != 0
+    }
+
+    pub fn is_switching_weapon(self: &InputEvent) -> bool {
+        self.buttons & INPUT_BUTTON_SWITCH_WEAPON != 0
+    }
 }
 
 #[derive(Event, Debug, Clone, Serialize, Deserialize)]
@@ -89,12 +191,14 @@ pub struct UpdateEvent {
     pub velocity: Option<SerializableVelocity>,
     pub includes_rollover: bool,
     pub handled_at: Option<f64>,
+    // what the player was last doing (if it's a player), for the client to draw
+    pub input: Option<InputEvent>,
 }
 
 #[derive(Event, Debug, Clone, Serialize, Deserialize)]
 pub struct DespawnEvent {
     pub entity_uuid: Uuid,
-    // one of "player" or "projectile"
+    // e.g. "player", "projectile" etc
     pub entity_type: String,
 }
 
@@ -102,3 +206,103 @@ pub struct DespawnEvent {
 pub struct LeaveEvent {
     pub player_uuid: Uuid,
 }
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct PingEvent {
+    // overwritten by the server with the session the ping arrived on
+    pub player_uuid: Uuid,
+    pub client_time: f64,
+    pub last_round_trip_time: Option<f64>,
+}
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct PongEvent {
+    pub player_uuid: Uuid,
+    pub client_time: f64,
+    pub server_time: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreboardEntry {
+    pub player_uuid: Uuid,
+    pub name: String,
+    pub color: Color,
+    pub score: i32,
+    pub health: f32,
+    pub max_health: f32,
+    pub ping: Option<f64>,
+    pub team: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamScoreboardEntry {
+    pub team: u32,
+    pub name: String,
+    pub color: Color,
+    pub score: i32,
+}
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreboardEvent {
+    pub server_time: f64,
+    pub entries: Vec<ScoreboardEntry>,
+    // empty for free-for-all
+    pub teams: Vec<TeamScoreboardEntry>,
+}
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct KillEvent {
+    // None if the victim wasn't killed by another player
+    pub killer_uuid: Option<Uuid>,
+    pub victim_uuid: Uuid,
+    pub server_time: f64,
+}
+
+// the state of a player's weapons, sent only to that player
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponEvent {
+    pub player_uuid: Uuid,
+    pub server_time: f64,
+    pub weapon_type: String,
+    pub weapon_types: Vec<String>,
+    // only for weapons with a magazine
+    pub ammo: Option<u32>,
+    pub magazine_size: Option<u32>,
+    pub reload_progress: Option<f32>,
+    // only for weapons that build up heat
+    pub heat: Option<f32>,
+    pub is_overheated: bool,
+}
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct RoundEvent {
+    pub server_time: f64,
+    pub round: Round,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagState {
+    pub flag_uuid: Uuid,
+    pub team: u32,
+    pub carrier_uuid: Option<Uuid>,
+    pub translation: Vec2,
+}
+
+// sent when an effect wears down (e.g. a shield soaking up damage)
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct EffectEvent {
+    pub effect_uuid: Uuid,
+    pub magnitude: f32,
+}
+
+#[derive(Event, Debug, Clone, Serialize, Deserialize)]
+pub struct GameModeEvent {
+    pub server_time: f64,
+    pub game_mode: String,
+    pub objective_name: Option<String>,
+    pub objective_limit: f32,
+    // indexed by team
+    pub objectives: Vec<f32>,
+    pub flags: Vec<FlagState>,
+    pub zone_team: Option<u32>,
+}
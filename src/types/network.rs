@@ -4,7 +4,9 @@ use uuid::Uuid;
 
 use crate::behaviour::collideable::CollisionEvent;
 use crate::types::event::{
-    DespawnEvent, InputEvent, JoinEvent, LeaveEvent, SpawnEvent, UpdateEvent,
+    DespawnEvent, EffectEvent, GameModeEvent, HelloEvent, InputEvent, JoinEvent, KillEvent,
+    LeaveEvent, PingEvent, PongEvent, RoundEvent, ScoreboardEvent, SpawnEvent, UpdateEvent,
+    WeaponEvent,
 };
 
 //
@@ -38,10 +40,12 @@ pub struct CloseEvent {
 // For serializing the Game layer into the WebSocket layer
 //
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Container {
     pub message_type: String,
-    // one of "join", "spawn", "input", "update", "despawn", "leave", "collision"
+    // one of "hello", "join", "spawn", "input", "update", "despawn", "leave", "collision", "ping", "pong",
+    // "scoreboard", "kill", "round", "weapon", "game_mode", "effect"
+    pub hello: Option<HelloEvent>,
     pub join: Option<JoinEvent>,
     pub spawn: Option<SpawnEvent>,
     pub input: Option<InputEvent>,
@@ -49,4 +53,12 @@ pub struct Container {
     pub despawn: Option<DespawnEvent>,
     pub leave: Option<LeaveEvent>,
     pub collision: Option<CollisionEvent>,
+    pub ping: Option<PingEvent>,
+    pub pong: Option<PongEvent>,
+    pub scoreboard: Option<ScoreboardEvent>,
+    pub kill: Option<KillEvent>,
+    pub round: Option<RoundEvent>,
+    pub weapon: Option<WeaponEvent>,
+    pub game_mode: Option<GameModeEvent>,
+    pub effect: Option<EffectEvent>,
 }